@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// A single day's puzzle. The input is parsed once and then shared between
+/// both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<String>;
+    fn part2(input: &Self::Input) -> Result<String>;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part {:?}, expected 1 or 2", s)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+/// The answer to one part, and how long it took to find.
+pub struct Answer {
+    pub part: Part,
+    pub answer: Result<String>,
+    pub solve_time: Duration,
+}
+
+/// The answers to every part that was asked for.
+pub struct Solved {
+    pub parse_time: Duration,
+    pub answers: Vec<Answer>,
+}
+
+/// Parses `input` once and solves each of `parts` against it. Fails outright
+/// only if the input can't be parsed; each part succeeds or fails separately.
+pub fn solve<S: Solution>(parts: &[Part], input: &str) -> Result<Solved> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::part1(&input),
+                Part::Two => S::part2(&input),
+            };
+            Answer {
+                part,
+                answer,
+                solve_time: start.elapsed(),
+            }
+        })
+        .collect();
+    Ok(Solved {
+        parse_time,
+        answers,
+    })
+}
+
+/// Error for a part that hasn't been solved yet.
+pub fn unsolved() -> Result<String> {
+    Err("not solved yet".into())
+}
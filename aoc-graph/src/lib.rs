@@ -0,0 +1,341 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+
+/// A set of tasks, some of which have to be finished before others can begin.
+#[derive(Debug, Clone)]
+pub struct TaskGraph<N> {
+    prereqs: HashMap<N, Vec<N>>,
+    dependers: HashMap<N, Vec<N>>,
+}
+
+/// Tasks that (transitively) depend on themselves, listed so that each one is
+/// a prerequisite of the next, and the last is a prerequisite of the first.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: fmt::Debug> fmt::Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "dependency cycle: ")?;
+        for node in self.0.iter() {
+            write!(f, "{:?} -> ", node)?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, "{:?}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N: fmt::Debug> Error for Cycle<N> {}
+
+impl<N: Ord + Hash + Clone> Default for TaskGraph<N> {
+    fn default() -> Self {
+        TaskGraph {
+            prereqs: HashMap::new(),
+            dependers: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Ord + Hash + Clone> TaskGraph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a task with no dependencies, if it isn't already in the graph.
+    pub fn add_node(&mut self, node: N) {
+        self.prereqs.entry(node.clone()).or_default();
+        self.dependers.entry(node).or_default();
+    }
+
+    /// Records that `prereq` must be finished before `depender` can begin.
+    pub fn add_dependency(&mut self, prereq: N, depender: N) {
+        self.add_node(prereq.clone());
+        self.add_node(depender.clone());
+        self.prereqs
+            .get_mut(&depender)
+            .unwrap()
+            .push(prereq.clone());
+        self.dependers.get_mut(&prereq).unwrap().push(depender);
+    }
+
+    pub fn len(&self) -> usize {
+        self.prereqs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prereqs.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.prereqs.contains_key(node)
+    }
+
+    /// Every task, in no particular order.
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.prereqs.keys()
+    }
+
+    /// The tasks that must be finished before `node` can begin.
+    pub fn prereqs(&self, node: &N) -> &[N] {
+        self.prereqs.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// The tasks that can't begin until `node` is finished.
+    pub fn dependers(&self, node: &N) -> &[N] {
+        self.dependers.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// How many prerequisites each task has.
+    pub fn number_of_prereqs(&self) -> HashMap<N, usize> {
+        self.prereqs
+            .iter()
+            .map(|(node, prereqs)| (node.clone(), prereqs.len()))
+            .collect()
+    }
+
+    /// Orders the tasks so that each comes after all of its prerequisites,
+    /// breaking ties by picking the smallest available task first.
+    pub fn topological_order(&self) -> Result<Vec<N>, Cycle<N>> {
+        let order = self.partial_order();
+        if order.len() < self.len() {
+            let done: HashSet<_> = order.into_iter().collect();
+            return Err(self.cycle_among(&done));
+        }
+        Ok(order)
+    }
+
+    /// The tasks that can never begin, because they're part of a cycle or
+    /// depend on one, in sorted order.
+    pub fn unreachable(&self) -> Vec<N> {
+        let done: HashSet<_> = self.partial_order().into_iter().collect();
+        let mut blocked: Vec<_> = self
+            .nodes()
+            .filter(|node| !done.contains(node))
+            .cloned()
+            .collect();
+        blocked.sort();
+        blocked
+    }
+
+    /// Like `topological_order`, but stops at the first point where every
+    /// remaining task is blocked.
+    fn partial_order(&self) -> Vec<N> {
+        let mut number_of_prereqs_left = self.number_of_prereqs();
+        let mut queue: BinaryHeap<_> = number_of_prereqs_left
+            .iter()
+            .filter(|(_, num_prereqs)| **num_prereqs == 0)
+            .map(|(node, _)| Reverse(node.clone()))
+            .collect();
+
+        let mut order = Vec::new();
+        while let Some(Reverse(node)) = queue.pop() {
+            for depender in self.dependers(&node) {
+                let num_prereqs =
+                    number_of_prereqs_left.get_mut(depender).unwrap();
+                *num_prereqs -= 1;
+                if *num_prereqs == 0 {
+                    queue.push(Reverse(depender.clone()));
+                }
+            }
+            order.push(node);
+        }
+        order
+    }
+
+    /// Finds a cycle, if there is one.
+    pub fn find_cycle(&self) -> Option<Cycle<N>> {
+        self.topological_order().err()
+    }
+
+    /// Every task not in `done` is blocked, so has a blocked prerequisite.
+    /// Following those back from any blocked task has to loop eventually.
+    fn cycle_among(&self, done: &HashSet<N>) -> Cycle<N> {
+        let blocked_prereq = |node: &N| {
+            self.prereqs(node)
+                .iter()
+                .filter(|prereq| !done.contains(prereq))
+                .min()
+                .unwrap()
+        };
+
+        let mut node = self.nodes().filter(|node| !done.contains(node)).min();
+        let mut path = Vec::new();
+        let mut seen = HashMap::new();
+        while let Some(current) = node {
+            if let Some(&start) = seen.get(current) {
+                let mut cycle: Vec<N> = path.drain(start..).collect();
+                cycle.reverse();
+                // Start from the smallest task, so the same cycle is always
+                // reported the same way
+                let smallest = (0..cycle.len()).min_by_key(|&i| &cycle[i]);
+                cycle.rotate_left(smallest.unwrap());
+                return Cycle(cycle);
+            }
+            seen.insert(current, path.len());
+            path.push(current.clone());
+            node = Some(blocked_prereq(current));
+        }
+        unreachable!("every task was finished, so there's no cycle")
+    }
+
+    /// The shortest time in which every task could be finished, with
+    /// unlimited workers and each task taking `duration(task)`.
+    pub fn critical_path_length<F>(&self, duration: F) -> Result<u64, Cycle<N>>
+    where
+        F: Fn(&N) -> u64,
+    {
+        let mut finish_times: HashMap<N, u64> = HashMap::new();
+        for node in self.topological_order()? {
+            let start = self
+                .prereqs(&node)
+                .iter()
+                .map(|prereq| finish_times[prereq])
+                .max()
+                .unwrap_or(0);
+            finish_times.insert(node.clone(), start + duration(&node));
+        }
+        Ok(finish_times.values().copied().max().unwrap_or(0))
+    }
+
+    /// Every valid ordering of the tasks, in lexicographic order, or `None`
+    /// if there are more than `limit` of them.
+    pub fn all_orderings(
+        &self,
+        limit: usize,
+    ) -> Result<Option<Vec<Vec<N>>>, Cycle<N>> {
+        self.topological_order()?;
+
+        let mut number_of_prereqs_left = self.number_of_prereqs();
+        let mut available: BTreeSet<N> = number_of_prereqs_left
+            .iter()
+            .filter(|(_, num_prereqs)| **num_prereqs == 0)
+            .map(|(node, _)| node.clone())
+            .collect();
+        let mut orderings = Vec::new();
+        let complete = self.extend_orderings(
+            &mut Vec::new(),
+            &mut available,
+            &mut number_of_prereqs_left,
+            &mut orderings,
+            limit,
+        );
+        Ok(if complete { Some(orderings) } else { None })
+    }
+
+    /// Adds every completion of `order` to `orderings`, returning false as
+    /// soon as there would be more than `limit`.
+    fn extend_orderings(
+        &self,
+        order: &mut Vec<N>,
+        available: &mut BTreeSet<N>,
+        number_of_prereqs_left: &mut HashMap<N, usize>,
+        orderings: &mut Vec<Vec<N>>,
+        limit: usize,
+    ) -> bool {
+        if order.len() == self.len() {
+            if orderings.len() == limit {
+                return false;
+            }
+            orderings.push(order.clone());
+            return true;
+        }
+
+        let choices: Vec<N> = available.iter().cloned().collect();
+        for node in choices {
+            available.remove(&node);
+            for depender in self.dependers(&node) {
+                let num_prereqs =
+                    number_of_prereqs_left.get_mut(depender).unwrap();
+                *num_prereqs -= 1;
+                if *num_prereqs == 0 {
+                    available.insert(depender.clone());
+                }
+            }
+            order.push(node.clone());
+
+            let complete = self.extend_orderings(
+                order,
+                available,
+                number_of_prereqs_left,
+                orderings,
+                limit,
+            );
+
+            order.pop();
+            for depender in self.dependers(&node) {
+                let num_prereqs =
+                    number_of_prereqs_left.get_mut(depender).unwrap();
+                if *num_prereqs == 0 {
+                    available.remove(depender);
+                }
+                *num_prereqs += 1;
+            }
+            available.insert(node);
+
+            if !complete {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> TaskGraph<char> {
+        let mut graph = TaskGraph::new();
+        for &(prereq, depender) in [
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ]
+        .iter()
+        {
+            graph.add_dependency(prereq, depender);
+        }
+        graph
+    }
+
+    #[test]
+    fn topological_order_is_lexicographic() {
+        let order = example().topological_order().unwrap();
+        assert_eq!(order, vec!['C', 'A', 'B', 'D', 'F', 'E']);
+    }
+
+    #[test]
+    fn critical_path() {
+        let duration = |task: &char| (*task as u8 - b'A' + 1) as u64;
+        assert_eq!(example().critical_path_length(duration), Ok(14));
+    }
+
+    #[test]
+    fn all_orderings_respects_limit() {
+        let graph = example();
+        let orderings = graph.all_orderings(8).unwrap().unwrap();
+        assert_eq!(orderings.len(), 8);
+        assert_eq!(orderings[0], graph.topological_order().unwrap());
+        assert!(orderings.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(graph.all_orderings(7), Ok(None));
+    }
+
+    #[test]
+    fn cycle_is_found() {
+        let mut graph = example();
+        graph.add_dependency('E', 'X');
+        graph.add_dependency('X', 'A');
+        graph.add_node('Z');
+        assert_eq!(graph.find_cycle(), Some(Cycle(vec!['A', 'B', 'E', 'X'])));
+        assert_eq!(graph.unreachable(), vec!['A', 'B', 'D', 'E', 'X']);
+        assert!(graph.all_orderings(10).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use nom::{
+    character::complete::{digit1, one_of},
+    combinator::{all_consuming, map_res, opt, recognize},
+    error::{context, ErrorKind, VerboseError, VerboseErrorKind},
+    sequence::pair,
+};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+pub type Res<T, U> = nom::IResult<T, U, VerboseError<T>>;
+
+pub trait Parse: Sized {
+    fn nom_parse(input: &str) -> Res<&str, Self>;
+}
+
+/// An unsigned decimal number, e.g. `42`.
+pub fn unsigned<T: FromStr>(input: &str) -> Res<&str, T> {
+    context("number", map_res(digit1, str::parse::<T>))(input)
+}
+
+/// A decimal number with an optional leading `+` or `-`, e.g. `-7` or `+3`.
+pub fn signed<T: FromStr>(input: &str) -> Res<&str, T> {
+    context(
+        "number",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse::<T>),
+    )(input)
+}
+
+/// The `u32` parser most days want.
+pub fn number(input: &str) -> Res<&str, u32> {
+    unsigned(input)
+}
+
+macro_rules! parse_numbers {
+    ($parser:ident: $($t:ty),*) => {
+        $(
+            impl Parse for $t {
+                fn nom_parse(input: &str) -> Res<&str, Self> {
+                    $parser(input)
+                }
+            }
+        )*
+    };
+}
+
+parse_numbers!(unsigned: u32, u64, usize);
+parse_numbers!(signed: i32, i64);
+
+/// A parse failure, located in the input and annotated with the `context`
+/// stack that was active when it happened.
+#[derive(Debug)]
+pub struct ParseError {
+    /// Name of the file the input came from, if it came from one.
+    pub file: Option<String>,
+    /// 1-indexed line of the input the error occurred on.
+    pub line: usize,
+    /// 1-indexed column (in characters) within that line.
+    pub column: usize,
+    /// The full text of the offending line.
+    pub line_text: String,
+    /// What the innermost parser was expecting.
+    pub expected: String,
+    /// `context` labels, outermost first.
+    pub context: Vec<&'static str>,
+}
+
+impl ParseError {
+    fn new(
+        input: &str,
+        first_line: usize,
+        err: nom::Err<VerboseError<&str>>,
+    ) -> Self {
+        let errors = match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => err.errors,
+            nom::Err::Incomplete(_) => Vec::new(),
+        };
+        let (location, expected) = match errors.first() {
+            Some((location, kind)) => (*location, describe(kind)),
+            None => ("", "more input".to_string()),
+        };
+        let context = errors
+            .iter()
+            .rev()
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(label) => Some(*label),
+                _ => None,
+            })
+            .collect();
+
+        let offset = input.len() - location.len();
+        let line_start = input[..offset].rfind('\n').map_or(0, |pos| pos + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |pos| offset + pos);
+        ParseError {
+            file: None,
+            line: first_line + input[..offset].matches('\n').count(),
+            column: input[line_start..offset].chars().count() + 1,
+            line_text: input[line_start..line_end].to_string(),
+            expected,
+            context,
+        }
+    }
+
+    /// Records the file the input was read from, for the report.
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+fn describe(kind: &VerboseErrorKind) -> String {
+    match kind {
+        VerboseErrorKind::Context(label) => label.to_string(),
+        VerboseErrorKind::Char(chr) => format!("'{}'", chr),
+        VerboseErrorKind::Nom(ErrorKind::Eof) => "end of line".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::Digit) => "a digit".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::MapRes) => "a valid value".to_string(),
+        VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let file = self.file.as_deref().unwrap_or("input");
+        writeln!(
+            f,
+            "failed to parse {} at line {}, column {}:",
+            file, self.line, self.column
+        )?;
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.line_text)?;
+        write!(
+            f,
+            "{} | {}^ expected {}",
+            gutter,
+            " ".repeat(self.column - 1),
+            self.expected
+        )?;
+        if !self.context.is_empty() {
+            write!(f, "\n{} = in {}", gutter, self.context.join(" > "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a single line as a `T`, failing if it has text left over. Errors
+/// are reported as being on line `line_number`.
+pub fn parse_line<T: Parse>(
+    line: &str,
+    line_number: usize,
+) -> Result<T, ParseError> {
+    all_consuming(T::nom_parse)(line)
+        .map(|(_, parsed)| parsed)
+        .map_err(|err| ParseError::new(line, line_number, err))
+}
+
+/// Parses every line of `input` as a `T`, failing if any line has text left
+/// over.
+pub fn parse_lines<T: Parse>(input: &str) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_line(line, index + 1))
+        .collect()
+}
+
+/// Parses all of `input` as a single `T`, ignoring trailing whitespace.
+pub fn parse_all<T: Parse>(input: &str) -> Result<T, ParseError> {
+    let input = input.trim_end();
+    all_consuming(T::nom_parse)(input)
+        .map(|(_, parsed)| parsed)
+        .map_err(|err| ParseError::new(input, 1, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(signed::<i32>("-12 rest"), Ok((" rest", -12)));
+        assert_eq!(signed::<i64>("+7"), Ok(("", 7)));
+        assert!(unsigned::<u32>("-7").is_err());
+        assert!(number("99999999999").is_err());
+    }
+
+    #[test]
+    fn parse_lines_rejects_leftovers() {
+        assert_eq!(parse_lines::<i32>("+1\n-2\n").unwrap(), vec![1, -2]);
+        let err = parse_lines::<i32>("+1\n-2 3\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.line_text, "-2 3");
+        assert_eq!(err.expected, "end of line");
+    }
+
+    #[test]
+    fn error_report() {
+        let err = parse_all::<u32>("12\nx").unwrap_err().in_file("input");
+        assert_eq!((err.line, err.column), (1, 3));
+        assert_eq!(
+            err.to_string(),
+            "failed to parse input at line 1, column 3:\n  |\n1 | 12\n  |   ^ expected end of line"
+        );
+    }
+}
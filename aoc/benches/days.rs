@@ -0,0 +1,32 @@
+use aoc_core::Solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::fs;
+
+/// Benchmarks parsing the day's real input, and solving each part from the
+/// already-parsed input.
+fn bench_day<S: Solution>(c: &mut Criterion, day: u32) {
+    let path = format!("{}/../day{:02}/input", env!("CARGO_MANIFEST_DIR"), day);
+    let input = fs::read_to_string(&path).unwrap();
+    let parsed = S::parse(&input).unwrap();
+
+    let mut group = c.benchmark_group(format!("day{:02}", day));
+    group.sample_size(10);
+    group.bench_function("parse", |b| b.iter(|| S::parse(&input).unwrap()));
+    group.bench_function("part1", |b| b.iter(|| S::part1(&parsed).unwrap()));
+    group.bench_function("part2", |b| b.iter(|| S::part2(&parsed).unwrap()));
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day01::Day01>(c, 1);
+    bench_day::<day02::Day02>(c, 2);
+    bench_day::<day03::Day03>(c, 3);
+    bench_day::<day04::Day04>(c, 4);
+    bench_day::<day05::Day05>(c, 5);
+    bench_day::<day06::Day06>(c, 6);
+    bench_day::<day07::Day07>(c, 7);
+    bench_day::<day08::Day08>(c, 8);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,51 @@
+use aoc_core::{Part, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Where the answers accepted by the Advent of Code site are recorded, in the
+/// workspace root.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Recorded answers, keyed like `[day01] part1 = "..."`.
+pub struct Answers(BTreeMap<String, BTreeMap<String, String>>);
+
+fn day_key(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+fn part_key(part: Part) -> String {
+    format!("part{}", part)
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let name = path.display();
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("couldn't read {}: {}", name, err))?;
+        let answers = toml::from_str(&contents)
+            .map_err(|err| format!("couldn't parse {}: {}", name, err))?;
+        Ok(Answers(answers))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(&self.0)?).map_err(|err| {
+            format!("couldn't write {}: {}", path.display(), err)
+        })?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.0
+            .get(&day_key(day))
+            .and_then(|parts| parts.get(&part_key(part)))
+            .map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: u32, part: Part, answer: String) {
+        self.0
+            .entry(day_key(day))
+            .or_default()
+            .insert(part_key(part), answer);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::input::{workspace_root, Input};
+use crate::solve;
+use aoc_core::{Part, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Where `--save-baseline` keeps timings to compare later runs against.
+pub fn baseline_path() -> PathBuf {
+    workspace_root().join("target/aoc-bench/baseline.toml")
+}
+
+/// Median times in seconds, keyed like `[day01] parse = ...`.
+pub type Timings = BTreeMap<String, BTreeMap<String, f64>>;
+
+fn median(mut times: Vec<Duration>) -> Duration {
+    times.sort();
+    times[times.len() / 2]
+}
+
+/// Solves every part of the day `runs` times, and returns the median time
+/// spent parsing and solving each part.
+pub fn time_day(day: u32, runs: usize) -> Result<BTreeMap<String, f64>> {
+    let parts = [Part::One, Part::Two];
+    let mut parse_times = Vec::new();
+    let mut solve_times = vec![Vec::new(); parts.len()];
+    for _ in 0..runs {
+        let solved = solve(day, &parts, &Input::Puzzle)?;
+        parse_times.push(solved.parse_time);
+        for (times, answer) in solve_times.iter_mut().zip(solved.answers) {
+            answer.answer?;
+            times.push(answer.solve_time);
+        }
+    }
+
+    let mut timings = BTreeMap::new();
+    timings.insert("parse".to_string(), median(parse_times).as_secs_f64());
+    for (part, times) in parts.iter().zip(solve_times) {
+        timings.insert(format!("part{}", part), median(times).as_secs_f64());
+    }
+    Ok(timings)
+}
+
+pub fn load_baseline() -> Result<Option<Timings>> {
+    let path = baseline_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(toml::from_str(&fs::read_to_string(path)?)?))
+}
+
+pub fn save_baseline(timings: &Timings) -> Result<()> {
+    let path = baseline_path();
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(path, toml::to_string(timings)?)?;
+    Ok(())
+}
+
+/// Prints a row per phase of each day, compared to the baseline if there is
+/// one.
+pub fn print_table(timings: &Timings, baseline: Option<&Timings>) {
+    println!("Day  Phase   Median      Baseline    Change");
+    for (day, phases) in timings.iter() {
+        for (phase, seconds) in phases.iter() {
+            let previous = baseline
+                .and_then(|baseline| baseline.get(day))
+                .and_then(|phases| phases.get(phase));
+            let (previous, change) = match previous {
+                Some(previous) => (
+                    format!("{:.1?}", Duration::from_secs_f64(*previous)),
+                    format!("{:+.1}%", (seconds / previous - 1.0) * 100.0),
+                ),
+                None => (String::new(), String::new()),
+            };
+            let row = format!(
+                "{:>3}  {:<6}  {:<10}  {:<10}  {}",
+                day.trim_start_matches("day").parse::<u32>().unwrap_or(0),
+                phase,
+                format!("{:.1?}", Duration::from_secs_f64(*seconds)),
+                previous,
+                change
+            );
+            println!("{}", row.trim_end());
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use aoc_core::Result;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Environment variable holding the adventofcode.com session cookie.
+pub const SESSION_VAR: &str = "ADVENT_SESSION";
+pub const DEFAULT_CACHE_DIR: &str = ".aoc-cache";
+/// The shortest gap allowed between two requests to the site.
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "github.com/nsunderland1/advent_of_code_2018 \
+                          by sunderland.nicholas@protonmail.com";
+
+#[derive(Debug)]
+pub enum FetchError {
+    MissingSession,
+    /// The site didn't accept the session cookie.
+    ExpiredSession,
+    /// The puzzle hasn't unlocked yet, or doesn't exist.
+    NotFound(u32),
+    RateLimited,
+    Status(u16),
+    /// The site answered with something that isn't a puzzle input, such as
+    /// an HTML error page.
+    NotInput,
+    Transport(String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::MissingSession => write!(
+                f,
+                "{} isn't set; copy the session cookie from adventofcode.com",
+                SESSION_VAR
+            ),
+            FetchError::ExpiredSession => write!(
+                f,
+                "the session in {} was rejected; log in again and copy the \
+                 new session cookie",
+                SESSION_VAR
+            ),
+            FetchError::NotFound(day) => {
+                write!(f, "the input for day {} isn't available yet", day)
+            }
+            FetchError::RateLimited => {
+                write!(f, "too many requests; wait a while before retrying")
+            }
+            FetchError::Status(status) => {
+                write!(f, "unexpected response status {}", status)
+            }
+            FetchError::NotInput => {
+                write!(f, "the response doesn't look like a puzzle input")
+            }
+            FetchError::Transport(err) => write!(f, "request failed: {}", err),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+/// Downloads puzzle inputs, keeping a copy of each one so it's only ever
+/// downloaded once.
+pub struct Fetcher {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub cache_dir: PathBuf,
+    pub min_interval: Duration,
+}
+
+impl Fetcher {
+    fn cache_path(&self, day: u32) -> PathBuf {
+        self.cache_dir.join(format!("2018-day{:02}", day))
+    }
+
+    fn last_request_path(&self) -> PathBuf {
+        self.cache_dir.join("last-request")
+    }
+
+    /// The input for `day`, from the cache if it's been downloaded before.
+    pub fn input(&self, day: u32) -> Result<String> {
+        if let Ok(input) = fs::read_to_string(self.cache_path(day)) {
+            return Ok(input);
+        }
+        let input = self.download(day)?;
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(self.cache_path(day), &input)?;
+        Ok(input)
+    }
+
+    /// Sleeps until at least `min_interval` has passed since the last
+    /// request, then records this one.
+    fn wait_for_turn(&self) -> Result<()> {
+        let last_request = fs::metadata(self.last_request_path())
+            .and_then(|metadata| metadata.modified());
+        if let Ok(last_request) = last_request {
+            let elapsed = SystemTime::now()
+                .duration_since(last_request)
+                .unwrap_or_default();
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(self.last_request_path(), "")?;
+        Ok(())
+    }
+
+    fn download(&self, day: u32) -> Result<String> {
+        let session =
+            self.session.as_ref().ok_or(FetchError::MissingSession)?;
+        self.wait_for_turn()?;
+
+        let url = format!("{}/2018/day/{}/input", self.base_url, day);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(400, _) | ureq::Error::Status(401, _) => {
+                    FetchError::ExpiredSession
+                }
+                ureq::Error::Status(404, _) => FetchError::NotFound(day),
+                ureq::Error::Status(429, _) => FetchError::RateLimited,
+                ureq::Error::Status(status, _) => FetchError::Status(status),
+                ureq::Error::Transport(err) => {
+                    FetchError::Transport(err.to_string())
+                }
+            })?;
+        let input = response.into_string()?;
+
+        // Logged-out requests sometimes get a 200 with an explanation
+        // instead of the input
+        if input.contains("Please log in") {
+            return Err(FetchError::ExpiredSession.into());
+        }
+        if input.trim().is_empty() || input.trim_start().starts_with('<') {
+            return Err(FetchError::NotInput.into());
+        }
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Serves each of `responses` to one request, in order, on a local port.
+    /// Returns the base URL to fetch from.
+    fn stand_in(responses: Vec<(u16, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\n\
+                     Connection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        format!("http://{}", address)
+    }
+
+    fn fetcher(name: &str, base_url: String) -> Fetcher {
+        let cache_dir = std::env::temp_dir().join(format!(
+            "aoc-fetch-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&cache_dir);
+        Fetcher {
+            base_url,
+            session: Some("secret".to_string()),
+            cache_dir,
+            min_interval: Duration::from_secs(0),
+        }
+    }
+
+    #[test]
+    fn downloads_once() {
+        let fetcher = fetcher("once", stand_in(vec![(200, "+1\n-2\n")]));
+        assert_eq!(fetcher.input(1).unwrap(), "+1\n-2\n");
+        // The stand-in only answers once, so this has to come from the cache
+        assert_eq!(fetcher.input(1).unwrap(), "+1\n-2\n");
+        fs::remove_dir_all(&fetcher.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn rejected_session() {
+        let body = "Puzzle inputs differ by user.  Please log in to get your \
+                    puzzle input.";
+        let fetcher = fetcher("rejected", stand_in(vec![(400, body)]));
+        let err = fetcher.input(1).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref(),
+            Some(FetchError::ExpiredSession)
+        ));
+        assert!(!fetcher.cache_path(1).exists());
+        let _ = fs::remove_dir_all(&fetcher.cache_dir);
+    }
+
+    #[test]
+    fn html_isnt_saved() {
+        let page = "<!DOCTYPE html><html>Oops</html>";
+        let fetcher = fetcher("html", stand_in(vec![(200, page)]));
+        let err = fetcher.input(1).unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(FetchError::NotInput)));
+        assert!(!fetcher.cache_path(1).exists());
+        let _ = fs::remove_dir_all(&fetcher.cache_dir);
+    }
+
+    #[test]
+    fn missing_session() {
+        let mut fetcher = fetcher("missing", "http://unused".to_string());
+        fetcher.session = None;
+        let err = fetcher.input(1).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref(),
+            Some(FetchError::MissingSession)
+        ));
+        let _ = fs::remove_dir_all(&fetcher.cache_dir);
+    }
+}
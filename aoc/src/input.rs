@@ -0,0 +1,80 @@
+use aoc_core::Result;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// The workspace the runner was built in, so inputs and answers are found
+/// wherever it's run from.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+/// Where a day's real puzzle input lives.
+pub fn input_path(day: u32) -> PathBuf {
+    workspace_root()
+        .join(format!("day{:02}", day))
+        .join("input")
+}
+
+/// Where to read a day's input from.
+pub enum Input {
+    /// The day's own `input` file.
+    Puzzle,
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl Input {
+    /// Picks the source from `path` (`-` meaning stdin) or `inline` text,
+    /// falling back to the puzzle input if neither is given.
+    pub fn new(path: Option<String>, inline: Option<String>) -> Self {
+        match (path, inline) {
+            (_, Some(inline)) => Input::Inline(inline),
+            (Some(path), None) if path == "-" => Input::Stdin,
+            (Some(path), None) => Input::File(path.into()),
+            (None, None) => Input::Puzzle,
+        }
+    }
+
+    /// Reads the input for `day`, along with a name to report errors in it
+    /// against.
+    pub fn read(&self, day: u32) -> Result<(String, String)> {
+        let read_file = |path: &Path| {
+            let name = path.display().to_string();
+            fs::read_to_string(path)
+                .map(|input| (name.clone(), input))
+                .map_err(|err| format!("couldn't read {}: {}", name, err))
+        };
+        match self {
+            Input::Puzzle => Ok(read_file(&input_path(day))?),
+            Input::File(path) => Ok(read_file(path)?),
+            Input::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(("<stdin>".to_string(), input))
+            }
+            Input::Inline(input) => {
+                Ok(("<input-str>".to_string(), input.clone()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources() {
+        let input = |path: Option<&str>, inline: Option<&str>| {
+            Input::new(path.map(String::from), inline.map(String::from))
+        };
+        assert!(matches!(input(None, None), Input::Puzzle));
+        assert!(matches!(input(Some("-"), None), Input::Stdin));
+        assert!(matches!(input(Some("x"), None), Input::File(_)));
+        let (name, text) = input(None, Some("+1")).read(1).unwrap();
+        assert_eq!((name.as_str(), text.as_str()), ("<input-str>", "+1"));
+        assert!(input_path(1).ends_with("day01/input"));
+    }
+}
@@ -0,0 +1,369 @@
+use aoc_core::{Answer, Part, Result, Solved};
+use aoc_parse::ParseError;
+use serde::Serialize;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+mod answers;
+mod bench;
+mod fetch;
+mod input;
+mod registry;
+mod scaffold;
+
+use answers::{Answers, ANSWERS_FILE};
+use fetch::Fetcher;
+use input::{input_path, workspace_root, Input};
+
+#[derive(StructOpt)]
+#[structopt(about = "Advent of Code 2018 solutions")]
+enum Command {
+    /// Solve one or all days against their puzzle input
+    Run {
+        /// Only run this day (defaults to every day)
+        #[structopt(long)]
+        day: Option<u32>,
+        /// Only run this part (defaults to both)
+        #[structopt(long)]
+        part: Option<Part>,
+        /// Read the day's input from this file instead, or `-` for stdin
+        #[structopt(requires = "day")]
+        input: Option<String>,
+        /// Use this text as the day's input instead
+        #[structopt(long, requires = "day", conflicts_with = "input")]
+        input_str: Option<String>,
+        /// Print answers as `text`, or as `json` with one record per line
+        #[structopt(long, default_value = "text")]
+        format: Format,
+    },
+    /// Check every day's answers against the recorded ones
+    #[structopt(alias = "test")]
+    Verify {
+        /// Only check this day (defaults to every day)
+        #[structopt(long)]
+        day: Option<u32>,
+        /// Only check this part (defaults to both)
+        #[structopt(long)]
+        part: Option<Part>,
+        /// Record the current answers as correct, e.g. after submitting them
+        #[structopt(long)]
+        record: bool,
+    },
+    /// Time parsing and solving each day, compared to the saved baseline
+    Bench {
+        /// Only time this day (defaults to every day)
+        #[structopt(long)]
+        day: Option<u32>,
+        /// How many times to run each day; the median time is reported
+        #[structopt(long, default_value = "10")]
+        runs: usize,
+        /// Save these timings as the baseline for future runs
+        #[structopt(long)]
+        save_baseline: bool,
+    },
+    /// Create a crate for a new day and add it to the runner
+    New {
+        /// The day to create
+        day: u32,
+    },
+    /// Download a day's puzzle input, using the ADVENT_SESSION cookie
+    Fetch {
+        /// The day to download the input for
+        #[structopt(long)]
+        day: u32,
+        /// The site to download from
+        #[structopt(long, env = "AOC_BASE_URL", default_value = fetch::DEFAULT_BASE_URL)]
+        base_url: String,
+        /// Where downloaded inputs are kept so they're never downloaded twice
+        /// (defaults to .aoc-cache in the workspace)
+        #[structopt(long)]
+        cache_dir: Option<PathBuf>,
+    },
+}
+
+/// How `run` prints answers.
+enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("invalid format {:?}, expected text or json", s)),
+        }
+    }
+}
+
+/// One part's answer, as printed by `--format json`. Exactly one of `answer`
+/// and `error` is set.
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u32,
+    part: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    parse_ms: f64,
+    solve_ms: f64,
+}
+
+/// Points a parse error at the file its input came from.
+fn in_file(err: Box<dyn Error>, path: &str) -> Box<dyn Error> {
+    match err.downcast::<ParseError>() {
+        Ok(err) => Box::new(err.in_file(path)),
+        Err(err) => err,
+    }
+}
+
+/// Solves each of `parts` against the day's input.
+fn solve(day: u32, parts: &[Part], input: &Input) -> Result<Solved> {
+    let (name, input) = input.read(day)?;
+    registry::solve(day, parts, &input).map_err(|err| in_file(err, &name))
+}
+
+/// Prints the answer to each part, returning whether every part succeeded.
+fn run(
+    day: u32,
+    parts: &[Part],
+    input: &Input,
+    format: &Format,
+) -> Result<bool> {
+    let solved = solve(day, parts, input)?;
+    let mut all_solved = true;
+    for Answer {
+        part,
+        answer,
+        solve_time,
+    } in solved.answers
+    {
+        all_solved &= answer.is_ok();
+        match format {
+            Format::Text => match answer {
+                Ok(answer) => {
+                    println!("Day {} part {}: {}", day, part, answer)
+                }
+                Err(err) => eprintln!("Day {} part {}: {}", day, part, err),
+            },
+            Format::Json => {
+                let record = Record {
+                    day,
+                    part: match part {
+                        Part::One => 1,
+                        Part::Two => 2,
+                    },
+                    answer: answer.as_deref().ok(),
+                    error: answer.as_ref().err().map(|err| err.to_string()),
+                    parse_ms: solved.parse_time.as_secs_f64() * 1000.0,
+                    solve_ms: solve_time.as_secs_f64() * 1000.0,
+                };
+                println!("{}", serde_json::to_string(&record)?);
+            }
+        }
+    }
+    Ok(all_solved)
+}
+
+/// How a part's answer compares to the recorded one.
+enum Status {
+    Pass,
+    Fail(String),
+    Missing,
+    Recorded,
+    Error,
+}
+
+impl Status {
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail(_) => "FAIL",
+            Status::Missing => "missing",
+            Status::Recorded => "recorded",
+            Status::Error => "ERROR",
+        }
+    }
+}
+
+/// Compares each part to the recorded answers, printing a row of the table
+/// for each, and returning whether none of them failed. With `record`, the
+/// current answers are saved as the correct ones instead.
+fn verify(
+    day: u32,
+    parts: &[Part],
+    answers: &mut Answers,
+    record: bool,
+) -> Result<bool> {
+    let mut all_passed = true;
+    for Answer { part, answer, .. } in
+        solve(day, parts, &Input::Puzzle)?.answers
+    {
+        let (status, answer) = match (answer, answers.get(day, part)) {
+            (Err(err), _) => (Status::Error, err.to_string()),
+            (Ok(answer), _) if record => {
+                answers.set(day, part, answer.clone());
+                (Status::Recorded, answer)
+            }
+            (Ok(answer), None) => (Status::Missing, answer),
+            (Ok(answer), Some(expected)) if answer == expected => {
+                (Status::Pass, answer)
+            }
+            (Ok(answer), Some(expected)) => {
+                (Status::Fail(expected.to_string()), answer)
+            }
+        };
+        let details = match &status {
+            Status::Fail(expected) => {
+                format!("{} (expected {})", answer, expected)
+            }
+            _ => answer,
+        };
+        if let Status::Fail(_) | Status::Error = status {
+            all_passed = false;
+        }
+        println!(
+            "{:>3}  {:>4}  {:<8}  {}",
+            day,
+            part,
+            status.label(),
+            details
+        );
+    }
+    Ok(all_passed)
+}
+
+/// The days to run, given an optional `--day`.
+fn days(day: Option<u32>) -> Result<Vec<u32>> {
+    match day {
+        Some(day) if registry::DAYS.contains(&day) => Ok(vec![day]),
+        Some(day) => Err(format!("no solution for day {}", day).into()),
+        None => Ok(registry::DAYS.to_vec()),
+    }
+}
+
+/// The parts to run, given an optional `--part`.
+fn parts(part: Option<Part>) -> Vec<Part> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    }
+}
+
+/// Runs `f` for each day, returning whether it succeeded for all of them.
+fn for_each_day<F>(days: Vec<u32>, mut f: F) -> bool
+where
+    F: FnMut(u32) -> Result<bool>,
+{
+    let mut all_ok = true;
+    for day in days {
+        match f(day) {
+            Ok(ok) => all_ok &= ok,
+            Err(err) => {
+                eprintln!("Day {}: {}", day, err);
+                all_ok = false;
+            }
+        }
+    }
+    all_ok
+}
+
+fn try_main() -> Result<bool> {
+    match Command::from_args() {
+        Command::Run {
+            day,
+            part,
+            input,
+            input_str,
+            format,
+        } => {
+            let parts = parts(part);
+            let input = Input::new(input, input_str);
+            Ok(for_each_day(days(day)?, |day| {
+                run(day, &parts, &input, &format)
+            }))
+        }
+        Command::Verify { day, part, record } => {
+            let answers_path = workspace_root().join(ANSWERS_FILE);
+            let mut answers = Answers::load(&answers_path)?;
+            let parts = parts(part);
+            println!("Day  Part  Status    Answer");
+            let all_passed = for_each_day(days(day)?, |day| {
+                verify(day, &parts, &mut answers, record)
+            });
+            if record {
+                answers.save(&answers_path)?;
+            }
+            Ok(all_passed)
+        }
+        Command::Bench {
+            day,
+            runs,
+            save_baseline,
+        } => {
+            let mut timings = bench::Timings::new();
+            let all_timed = for_each_day(days(day)?, |day| {
+                let day_timings = bench::time_day(day, runs.max(1))?;
+                timings.insert(format!("day{:02}", day), day_timings);
+                Ok(true)
+            });
+            bench::print_table(&timings, bench::load_baseline()?.as_ref());
+            if save_baseline {
+                bench::save_baseline(&timings)?;
+                println!(
+                    "Saved baseline to {}",
+                    bench::baseline_path().display()
+                );
+            }
+            Ok(all_timed)
+        }
+        Command::New { day } => {
+            scaffold::new_day(workspace_root(), day)?;
+            println!(
+                "Created day{:02}; download its input with `aoc fetch --day {}`",
+                day, day
+            );
+            Ok(true)
+        }
+        Command::Fetch {
+            day,
+            base_url,
+            cache_dir,
+        } => {
+            let fetcher = Fetcher {
+                base_url,
+                session: env::var(fetch::SESSION_VAR).ok(),
+                cache_dir: cache_dir.unwrap_or_else(|| {
+                    workspace_root().join(fetch::DEFAULT_CACHE_DIR)
+                }),
+                min_interval: fetch::MIN_REQUEST_INTERVAL,
+            };
+            let input = fetcher.input(day)?;
+            let path = input_path(day);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, input)?;
+            println!("Saved day {} input to {}", day, path.display());
+            Ok(true)
+        }
+    }
+}
+
+fn main() {
+    match try_main() {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use aoc_core::{solve as solve_with, Part, Result, Solved};
+
+macro_rules! solutions {
+    ($($day:literal => $solution:ty,)*) => {
+        /// Every day with a solution, in order.
+        pub const DAYS: &[u32] = &[$($day),*];
+
+        pub fn solve(day: u32, parts: &[Part], input: &str) -> Result<Solved> {
+            match day {
+                $($day => solve_with::<$solution>(parts, input),)*
+                _ => Err(format!("no solution for day {}", day).into()),
+            }
+        }
+    };
+}
+
+// `aoc new` adds a line here for each new day
+solutions! {
+    1 => day01::Day01,
+    2 => day02::Day02,
+    3 => day03::Day03,
+    4 => day04::Day04,
+    5 => day05::Day05,
+    6 => day06::Day06,
+    7 => day07::Day07,
+    8 => day08::Day08,
+}
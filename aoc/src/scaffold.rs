@@ -0,0 +1,216 @@
+use aoc_core::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.tmpl");
+
+/// The day a line of one of the lists of days refers to, going by the first
+/// `dayNN` in it.
+fn day_of(line: &str) -> Option<u32> {
+    line.match_indices("day").find_map(|(index, _)| {
+        let digits = line.get(index + 3..index + 5)?;
+        if digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            digits.parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Inserts `new_line` among the lines that `is_entry` picks out, keeping them
+/// ordered by day.
+fn insert_day_line<F>(
+    contents: &str,
+    day: u32,
+    new_line: &str,
+    is_entry: F,
+) -> Result<String>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut lines: Vec<&str> = contents.lines().collect();
+    let entries: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_entry(line.trim()))
+        .filter_map(|(index, line)| Some((index, day_of(line)?)))
+        .collect();
+    let last = entries.last().ok_or("couldn't find the list of days")?;
+    if entries.iter().any(|(_, entry_day)| *entry_day == day) {
+        return Err(format!("day {} is already listed", day).into());
+    }
+    let index = entries
+        .iter()
+        .find(|(_, entry_day)| *entry_day > day)
+        .map_or(last.0 + 1, |(index, _)| *index);
+    lines.insert(index, new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Reads the file at `path` and returns what `f` would change it to, without
+/// writing anything.
+fn edit<F>(path: &Path, f: F) -> Result<(PathBuf, String)>
+where
+    F: FnOnce(&str) -> Result<String>,
+{
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("couldn't read {}: {}", path.display(), err))?;
+    Ok((path.to_path_buf(), f(&contents)?))
+}
+
+fn write_crate(dir: &Path, padded: &str) -> Result<()> {
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(
+        dir.join("Cargo.toml"),
+        CARGO_TEMPLATE.replace("{{DAY}}", padded),
+    )?;
+    fs::write(
+        dir.join("src/lib.rs"),
+        LIB_TEMPLATE.replace("{{DAY}}", padded),
+    )?;
+    Ok(())
+}
+
+/// Creates a crate for `day` under `root` from the templates, and adds it to
+/// the workspace, the runner's registry and the benchmarks. Every list is
+/// checked before anything is written, and the crate is removed again if
+/// writing fails.
+pub fn new_day(root: &Path, day: u32) -> Result<()> {
+    if !(1..=25).contains(&day) {
+        return Err(
+            format!("there's no day {}; days go from 1 to 25", day).into()
+        );
+    }
+    let name = format!("day{:02}", day);
+    let dir = root.join(&name);
+    if dir.join("Cargo.toml").exists() {
+        return Err(format!("{} already exists", name).into());
+    }
+
+    let padded = format!("{:02}", day);
+    let edits = vec![
+        edit(&root.join("Cargo.toml"), |contents| {
+            let line = format!("    \"{}\",", name);
+            insert_day_line(contents, day, &line, |line| {
+                line.starts_with("\"day")
+            })
+        })?,
+        edit(&root.join("aoc/Cargo.toml"), |contents| {
+            let line = format!("{} = {{ path = \"../{}\" }}", name, name);
+            insert_day_line(contents, day, &line, |line| {
+                line.starts_with("day")
+            })
+        })?,
+        edit(&root.join("aoc/src/registry.rs"), |contents| {
+            let line = format!("    {} => {}::Day{},", day, name, padded);
+            insert_day_line(contents, day, &line, |line| {
+                line.contains("=> day")
+            })
+        })?,
+        edit(&root.join("aoc/benches/days.rs"), |contents| {
+            let line = format!(
+                "    bench_day::<{}::Day{}>(c, {});",
+                name, padded, day
+            );
+            insert_day_line(contents, day, &line, |line| {
+                line.starts_with("bench_day::<day")
+            })
+        })?,
+    ];
+
+    // The directory may already be there holding a downloaded input, in
+    // which case only the files written here are cleaned up
+    let dir_existed = dir.exists();
+    let written = write_crate(&dir, &padded).and_then(|()| {
+        for (path, contents) in edits {
+            fs::write(path, contents)?;
+        }
+        Ok(())
+    });
+    if written.is_err() {
+        if dir_existed {
+            let _ = fs::remove_file(dir.join("Cargo.toml"));
+            let _ = fs::remove_dir_all(dir.join("src"));
+        } else {
+            let _ = fs::remove_dir_all(&dir);
+        }
+    }
+    written
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_lines_stay_sorted() {
+        let contents = "[workspace]\nmembers = [\n    \"aoc\",\n    \
+                        \"day01\",\n    \"day03\",\n]\n";
+        let is_member = |line: &str| line.starts_with("\"day");
+        assert_eq!(
+            insert_day_line(contents, 2, "    \"day02\",", is_member).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \
+             \"day02\",\n    \"day03\",\n]\n"
+        );
+        assert_eq!(
+            insert_day_line(contents, 4, "    \"day04\",", is_member).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \
+             \"day03\",\n    \"day04\",\n]\n"
+        );
+        assert!(
+            insert_day_line(contents, 3, "    \"day03\",", is_member).is_err()
+        );
+    }
+
+    #[test]
+    fn new_day_is_wired_in() {
+        let root = std::env::temp_dir()
+            .join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for file in &[
+            "Cargo.toml",
+            "aoc/Cargo.toml",
+            "aoc/src/registry.rs",
+            "aoc/benches/days.rs",
+        ] {
+            fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            fs::copy(workspace.join(file), root.join(file)).unwrap();
+        }
+
+        new_day(&root, 25).unwrap();
+        let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+        assert!(read("day25/src/lib.rs").contains("impl Solution for Day25"));
+        assert!(read("day25/Cargo.toml").contains("name = \"day25\""));
+        assert!(read("Cargo.toml").contains("    \"day25\",\n]"));
+        assert!(
+            read("aoc/Cargo.toml").contains("day25 = { path = \"../day25\" }")
+        );
+        assert!(
+            read("aoc/src/registry.rs").contains("    25 => day25::Day25,\n}")
+        );
+        assert!(read("aoc/benches/days.rs")
+            .contains("    bench_day::<day25::Day25>(c, 25);\n}"));
+        assert!(new_day(&root, 25).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn bad_days_leave_nothing_behind() {
+        let root = std::env::temp_dir()
+            .join(format!("aoc-scaffold-bad-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        assert!(new_day(&root, 0).is_err());
+        assert!(new_day(&root, 100).is_err());
+        assert!(!root.join("day00").exists());
+        assert!(!root.join("day100").exists());
+
+        // None of the lists of days are there to add to
+        assert!(new_day(&root, 9).is_err());
+        assert!(!root.join("day09").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
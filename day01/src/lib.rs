@@ -0,0 +1,292 @@
+use aoc_core::{Result, Solution};
+use aoc_parse::{parse_line, parse_lines};
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+
+pub struct Day01;
+
+/// Applies adjustments one at a time as they arrive, keeping track of the
+/// frequency and every frequency reached so far.
+#[derive(Debug, Clone)]
+pub struct Calibrator {
+    frequency: i64,
+    seen: HashSet<i64>,
+    first_repeat: Option<i64>,
+}
+
+impl Default for Calibrator {
+    fn default() -> Self {
+        let mut seen = HashSet::new();
+        seen.insert(0);
+        Calibrator {
+            frequency: 0,
+            seen,
+            first_repeat: None,
+        }
+    }
+}
+
+impl Calibrator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The sum of every adjustment so far.
+    pub fn frequency(&self) -> i64 {
+        self.frequency
+    }
+
+    /// The first frequency that's been reached twice, if any has.
+    pub fn first_repeat(&self) -> Option<i64> {
+        self.first_repeat
+    }
+
+    /// Applies one adjustment. Returns the new frequency if this is the
+    /// first time any frequency has been reached twice. Fails, leaving the
+    /// frequency as it was, if it would overflow.
+    pub fn adjust(&mut self, adjustment: i64) -> Result<Option<i64>> {
+        self.frequency = self
+            .frequency
+            .checked_add(adjustment)
+            .ok_or("the frequency overflowed")?;
+        if self.first_repeat.is_none() && !self.seen.insert(self.frequency) {
+            self.first_repeat = Some(self.frequency);
+            return Ok(self.first_repeat);
+        }
+        Ok(None)
+    }
+
+    /// Applies the adjustments in `reader`, one per line, as they're read,
+    /// stopping as soon as a frequency is repeated and returning it. Returns
+    /// `None` if the reader runs out first. If a frequency has already been
+    /// repeated, returns that one without reading anything.
+    pub fn read_until_repeat<R: BufRead>(
+        &mut self,
+        reader: R,
+    ) -> Result<Option<i64>> {
+        if self.first_repeat.is_some() {
+            return Ok(self.first_repeat);
+        }
+        for (index, line) in reader.lines().enumerate() {
+            let line_number = index + 1;
+            let adjustment = parse_line(&line?, line_number)?;
+            let repeat = self
+                .adjust(adjustment)
+                .map_err(|err| format!("line {}: {}", line_number, err))?;
+            if repeat.is_some() {
+                return Ok(repeat);
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// The first frequency reached twice while applying `adjustments` over and
+/// over, or `None` if no frequency is ever repeated. Fails if the frequency
+/// overflows an `i64` during the first pass.
+///
+/// Each full pass shifts every frequency by the total drift, so the frequency
+/// before adjustment `i` on pass `k` is `prefix_sums[i] + k * drift`. After
+/// the first pass, a frequency can only come back by drifting onto another
+/// prefix sum that's congruent to it modulo the drift, and the nearest one in
+/// the direction of drift is the first it'll reach.
+pub fn first_repeated_frequency(adjustments: &[i64]) -> Result<Option<i64>> {
+    let mut prefix_sums = Vec::with_capacity(adjustments.len());
+    let mut seen = HashSet::new();
+    let mut frequency: i64 = 0;
+    for adjustment in adjustments {
+        if !seen.insert(frequency) {
+            return Ok(Some(frequency));
+        }
+        prefix_sums.push(frequency);
+        frequency = frequency
+            .checked_add(*adjustment)
+            .ok_or("the frequency overflowed")?;
+    }
+    let drift = frequency;
+    if adjustments.is_empty() {
+        return Ok(None);
+    }
+    if drift == 0 {
+        // Every pass ends back at the start
+        return Ok(Some(0));
+    }
+
+    let mut classes: HashMap<i64, Vec<(i64, usize)>> = HashMap::new();
+    for (index, &sum) in prefix_sums.iter().enumerate() {
+        classes
+            .entry(sum.wrapping_rem_euclid(drift))
+            .or_default()
+            .push((sum, index));
+    }
+    // Widened, since prefix sums can be nearly 2^64 apart
+    let passes_len = adjustments.len() as u128;
+    let repeat = classes
+        .values_mut()
+        .flat_map(|class| {
+            class.sort_unstable();
+            if drift < 0 {
+                class.reverse();
+            }
+            class.windows(2).map(|pair| {
+                let ((from, index), (to, _)) = (pair[0], pair[1]);
+                let passes =
+                    ((to as i128 - from as i128) / drift as i128) as u128;
+                (passes * passes_len + index as u128, to)
+            })
+        })
+        .min()
+        .map(|(_, frequency)| frequency);
+    Ok(repeat)
+}
+
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_lines(input)?)
+    }
+
+    fn part1(adjustments: &Self::Input) -> Result<String> {
+        let sum = adjustments
+            .iter()
+            .try_fold(0i64, |sum, &adjustment| sum.checked_add(adjustment))
+            .ok_or("the frequency overflowed")?;
+        Ok(sum.to_string())
+    }
+
+    fn part2(adjustments: &Self::Input) -> Result<String> {
+        let frequency = first_repeated_frequency(adjustments)?
+            .ok_or("the frequency never repeats")?;
+        Ok(frequency.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(part: fn(&Vec<i64>) -> Result<String>, input: &str) -> String {
+        let adjustments = Day01::parse(&input.replace(", ", "\n")).unwrap();
+        part(&adjustments).unwrap()
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(solve(Day01::part1, "+1, -2, +3, +1"), "3");
+        assert_eq!(solve(Day01::part1, "+1, +1, +1"), "3");
+        assert_eq!(solve(Day01::part1, "+1, +1, -2"), "0");
+        assert_eq!(solve(Day01::part1, "-1, -2, -3"), "-6");
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(solve(Day01::part2, "+1, -2, +3, +1"), "2");
+        assert_eq!(solve(Day01::part2, "+1, -1"), "0");
+        assert_eq!(solve(Day01::part2, "+3, +3, +4, -2, -4"), "10");
+        assert_eq!(solve(Day01::part2, "-6, +3, +8, +5, -6"), "5");
+        assert_eq!(solve(Day01::part2, "+7, +7, -2, -7, -4"), "14");
+    }
+
+    #[test]
+    fn calibrates_from_reader() {
+        let mut calibrator = Calibrator::new();
+        let reader = "+1\n-2\n+3\n+1\n+1\n-2\nnot a number\n".as_bytes();
+        assert_eq!(calibrator.read_until_repeat(reader).unwrap(), Some(2));
+        // It stops reading at the repeat, so never reaches the bad line
+        assert_eq!(calibrator.frequency(), 2);
+        assert_eq!(calibrator.first_repeat(), Some(2));
+
+        let mut calibrator = Calibrator::new();
+        let reader = "+5000000000\n+5000000000\n".as_bytes();
+        assert_eq!(calibrator.read_until_repeat(reader).unwrap(), None);
+        assert_eq!(calibrator.frequency(), 10_000_000_000);
+        let err = calibrator.read_until_repeat("+1\nx\n".as_bytes());
+        assert!(err.unwrap_err().to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn calibrator_overflow() {
+        let mut calibrator = Calibrator::new();
+        assert_eq!(calibrator.adjust(i64::MAX).unwrap(), None);
+        assert!(calibrator.adjust(1).is_err());
+        assert_eq!(calibrator.frequency(), i64::MAX);
+
+        let mut calibrator = Calibrator::new();
+        let reader = "+9223372036854775807\n-1\n+2\n".as_bytes();
+        let err = calibrator.read_until_repeat(reader).unwrap_err();
+        assert_eq!(err.to_string(), "line 3: the frequency overflowed");
+    }
+
+    #[test]
+    fn repeat_is_remembered() {
+        let mut calibrator = Calibrator::new();
+        let reader = "+1\n-1\n+5\n".as_bytes();
+        assert_eq!(calibrator.read_until_repeat(reader).unwrap(), Some(0));
+        let reader = "+1\n+1\n".as_bytes();
+        assert_eq!(calibrator.read_until_repeat(reader).unwrap(), Some(0));
+        // Nothing more was read
+        assert_eq!(calibrator.frequency(), 0);
+    }
+
+    #[test]
+    fn never_repeats() {
+        assert_eq!(first_repeated_frequency(&[1, 1]).unwrap(), None);
+        assert_eq!(first_repeated_frequency(&[5, -2]).unwrap(), None);
+        assert_eq!(first_repeated_frequency(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn large_frequencies() {
+        let adjustments = Day01::parse("+9223372036854775807\n+1\n").unwrap();
+        let err = Day01::part1(&adjustments).unwrap_err();
+        assert_eq!(err.to_string(), "the frequency overflowed");
+        let adjustments = Day01::parse("+9223372036854775807\n-1\n").unwrap();
+        assert_eq!(Day01::part1(&adjustments).unwrap(), "9223372036854775806");
+        assert!(first_repeated_frequency(&[i64::MAX, 1]).is_err());
+        // Frequencies far apart, which drift onto each other after many
+        // passes
+        let big = i64::MAX / 2;
+        assert_eq!(
+            first_repeated_frequency(&[-big, 2 * big, -big + 1]).unwrap(),
+            Some(big)
+        );
+        assert_eq!(
+            first_repeated_frequency(&[i64::MIN, i64::MAX, 1]).unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            first_repeated_frequency(&[i64::MIN, i64::MAX]).unwrap(),
+            Some(i64::MIN)
+        );
+    }
+
+    #[test]
+    fn matches_simulation() {
+        let simulate = |adjustments: &[i64]| {
+            let mut seen = HashSet::new();
+            let mut frequency = 0;
+            for adjustment in adjustments.iter().cycle() {
+                if !seen.insert(frequency) {
+                    return frequency;
+                }
+                frequency += adjustment;
+            }
+            unreachable!()
+        };
+        for adjustments in &[
+            vec![3, -5, 4],
+            vec![-4, 7, -5],
+            vec![2, -7, 3, 1, 4],
+            vec![-10, 4, 3, 9, -7, -1],
+            vec![5, -2, -2],
+        ] {
+            assert_eq!(
+                first_repeated_frequency(adjustments).unwrap(),
+                Some(simulate(adjustments)),
+                "{:?}",
+                adjustments
+            );
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use aoc_core::{Result, Solution};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+pub struct Day02;
+
+/// The IDs counted towards a checksum, so it can be checked by hand.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Checksum {
+    /// For each multiplicity, the indices of the IDs that have some byte
+    /// exactly that many times.
+    pub buckets: BTreeMap<usize, Vec<usize>>,
+}
+
+impl Checksum {
+    /// The product of the number of IDs in each bucket.
+    pub fn value(&self) -> usize {
+        self.buckets.values().map(Vec::len).product()
+    }
+}
+
+/// Buckets the IDs by which of `multiplicities` some byte in them appears
+/// exactly that many times. An ID with several bytes repeated the same
+/// number of times is only counted once in that bucket.
+pub fn checksum(ids: &[String], multiplicities: &[usize]) -> Checksum {
+    let mut buckets: BTreeMap<usize, Vec<usize>> = multiplicities
+        .iter()
+        .map(|&multiplicity| (multiplicity, Vec::new()))
+        .collect();
+    for (index, id) in ids.iter().enumerate() {
+        let mut counts = [0; 256];
+        for byte in id.bytes() {
+            counts[byte as usize] += 1;
+        }
+        for (multiplicity, contributors) in buckets.iter_mut() {
+            if counts.iter().any(|count| count == multiplicity) {
+                contributors.push(index);
+            }
+        }
+    }
+    Checksum { buckets }
+}
+
+/// Every way of picking `k` of the positions `0..n`, in lexicographic order.
+fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    let mut all = Vec::new();
+    let mut chosen: Vec<usize> = (0..k).collect();
+    loop {
+        all.push(chosen.clone());
+        // Advance the rightmost position that still has room to move
+        let movable = (0..k).rev().find(|&i| chosen[i] < n - k + i);
+        match movable {
+            Some(i) => {
+                chosen[i] += 1;
+                for j in i + 1..k {
+                    chosen[j] = chosen[j - 1] + 1;
+                }
+            }
+            None => return all,
+        }
+    }
+}
+
+/// Every pair of indices `(i, j)`, with `i < j`, of IDs that differ in at
+/// most `max_distance` positions, in sorted order. The IDs all have to be the
+/// same length.
+///
+/// Rather than comparing every pair, each ID is bucketed once for each way of
+/// masking out `max_distance` of its positions: two IDs are close exactly
+/// when they agree everywhere else under some mask.
+pub fn near_pairs(
+    ids: &[String],
+    max_distance: usize,
+) -> Result<Vec<(usize, usize)>> {
+    let len = match ids.first() {
+        Some(id) => id.len(),
+        None => return Ok(Vec::new()),
+    };
+    if ids.iter().any(|id| id.len() != len) {
+        return Err("IDs aren't all the same length".into());
+    }
+
+    let mut pairs = BTreeSet::new();
+    for masked in combinations(len, max_distance.min(len)) {
+        let mut buckets: HashMap<Vec<u8>, Vec<usize>> = HashMap::new();
+        for (index, id) in ids.iter().enumerate() {
+            let key = id
+                .bytes()
+                .enumerate()
+                .filter(|(pos, _)| !masked.contains(pos))
+                .map(|(_, byte)| byte)
+                .collect();
+            buckets.entry(key).or_default().push(index);
+        }
+        for bucket in buckets.values() {
+            for (pos, &i) in bucket.iter().enumerate() {
+                for &j in &bucket[pos + 1..] {
+                    pairs.insert((i, j));
+                }
+            }
+        }
+    }
+    Ok(pairs.into_iter().collect())
+}
+
+/// The characters two IDs have in common, in the same position.
+pub fn common_letters(id1: &str, id2: &str) -> String {
+    id1.chars()
+        .zip(id2.chars())
+        .filter(|(a, b)| a == b)
+        .map(|(a, _)| a)
+        .collect()
+}
+
+impl Solution for Day02 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(lines: &Self::Input) -> Result<String> {
+        Ok(checksum(lines, &[2, 3]).value().to_string())
+    }
+
+    fn part2(lines: &Self::Input) -> Result<String> {
+        near_pairs(lines, 1)?
+            .into_iter()
+            // Identical IDs are close too, but aren't the pair we want
+            .find(|&(i, j)| lines[i] != lines[j])
+            .map(|(i, j)| common_letters(&lines[i], &lines[j]))
+            .ok_or_else(|| {
+                "no pair of IDs differs by exactly one character".into()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let ids = Day02::parse(
+            "abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\n",
+        )
+        .unwrap();
+        assert_eq!(Day02::part1(&ids).unwrap(), "12");
+    }
+
+    #[test]
+    fn checksum_buckets() {
+        let ids = Day02::parse(
+            "abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\n",
+        )
+        .unwrap();
+        let checksum = checksum(&ids, &[1, 2, 3]);
+        assert_eq!(checksum.buckets[&2], vec![1, 2, 4, 5]);
+        assert_eq!(checksum.buckets[&3], vec![1, 3, 6]);
+        assert_eq!(checksum.buckets[&1], vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(checksum.value(), 72);
+    }
+
+    #[test]
+    fn part2_example() {
+        let ids =
+            Day02::parse("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz\n")
+                .unwrap();
+        assert_eq!(Day02::part2(&ids).unwrap(), "fgij");
+    }
+
+    #[test]
+    fn near_pairs_within_distance() {
+        let ids = Day02::parse("abcd\nabce\nxbcf\nabcd\nwxyz\n").unwrap();
+        assert_eq!(near_pairs(&ids, 0).unwrap(), vec![(0, 3)]);
+        assert_eq!(near_pairs(&ids, 1).unwrap(), vec![(0, 1), (0, 3), (1, 3)]);
+        assert_eq!(
+            near_pairs(&ids, 2).unwrap(),
+            vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]
+        );
+        assert_eq!(near_pairs(&ids, 4).unwrap().len(), 10);
+        assert!(near_pairs(&Day02::parse("ab\nabc\n").unwrap(), 1).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+//! Draws a set of claims, e.g.
+//! `cargo run -p day03 --example render -- day03/input fabric.png`, or prints
+//! them as text when no output file is given.
+
+use aoc_core::{Result, Solution};
+use day03::{Day03, Heatmap};
+use std::env;
+use std::fs::{self, File};
+use std::io::BufWriter;
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (input, output) = match &args[..] {
+        [input] => (input, None),
+        [input, output] => (input, Some(output)),
+        _ => return Err("usage: render INPUT [OUTPUT.png]".into()),
+    };
+    let claims = Day03::parse(&fs::read_to_string(input)?)?;
+    let heatmap = Heatmap::new(&claims)?;
+    match output {
+        Some(output) => {
+            heatmap.write_png(BufWriter::new(File::create(output)?))
+        }
+        None => {
+            print!("{}", heatmap.to_ascii());
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use crate::overlap::coverage;
+use crate::{Claim, ClaimError, ClaimID, Dimensions, Position, Rect};
+use std::collections::{BTreeSet, HashMap};
+use std::convert::TryFrom;
+
+/// The side of the square cells the fabric is split into for lookups.
+const CELL_SIZE: u64 = 64;
+/// Claims spanning more cells than this are kept aside and checked against
+/// every query, rather than being added to each cell.
+const MAX_CELLS: u64 = 1024;
+
+type Cell = (u64, u64);
+
+/// A changing set of claims, indexed by where they are, that keeps track of
+/// how much fabric is contested as claims are added and removed.
+#[derive(Debug, Default, Clone)]
+pub struct ClaimIndex {
+    claims: HashMap<ClaimID, (Claim, Rect)>,
+    cells: HashMap<Cell, Vec<ClaimID>>,
+    oversized: Vec<ClaimID>,
+    contested_area: u128,
+}
+
+/// The cells a rectangle covers some of.
+fn cells(rect: &Rect) -> impl Iterator<Item = Cell> {
+    let columns = rect.left() / CELL_SIZE..=(rect.right() - 1) / CELL_SIZE;
+    let rows = rect.top() / CELL_SIZE..=(rect.bottom() - 1) / CELL_SIZE;
+    columns.flat_map(move |x| rows.clone().map(move |y| (x, y)))
+}
+
+fn number_of_cells(rect: &Rect) -> u64 {
+    let columns = (rect.right() - 1) / CELL_SIZE - rect.left() / CELL_SIZE + 1;
+    let rows = (rect.bottom() - 1) / CELL_SIZE - rect.top() / CELL_SIZE + 1;
+    columns.saturating_mul(rows)
+}
+
+impl ClaimIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.claims.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.claims.is_empty()
+    }
+
+    pub fn get(&self, id: ClaimID) -> Option<&Claim> {
+        self.claims.get(&id).map(|(claim, _)| claim)
+    }
+
+    /// The number of square inches covered by two or more claims.
+    pub fn contested_area(&self) -> u128 {
+        self.contested_area
+    }
+
+    /// How much of `rect` the other claims in the index cover once, so
+    /// would become contested if `rect` were added.
+    fn newly_contested(&self, rect: &Rect, id: ClaimID) -> u128 {
+        let clipped: Vec<_> = self
+            .intersecting(rect)
+            .into_iter()
+            .filter(|&other| other != id)
+            .filter_map(|other| self.claims[&other].1.intersection(rect))
+            .collect();
+        let (once, twice) = coverage(&clipped);
+        once - twice
+    }
+
+    /// Adds a claim. Empty claims are rejected, since they can't cover any
+    /// fabric, as are claims with the same ID as one already in the index.
+    pub fn insert(&mut self, claim: Claim) -> Result<(), ClaimError> {
+        let rect = Rect::try_from(&claim)?;
+        let id = claim.id;
+        if self.claims.contains_key(&id) {
+            return Err(ClaimError::Duplicate(id));
+        }
+
+        self.contested_area += self.newly_contested(&rect, id);
+        if number_of_cells(&rect) > MAX_CELLS {
+            self.oversized.push(id);
+        } else {
+            for cell in cells(&rect) {
+                self.cells.entry(cell).or_default().push(id);
+            }
+        }
+        self.claims.insert(id, (claim, rect));
+        Ok(())
+    }
+
+    /// Removes the claim with ID `id`, returning it if there was one.
+    pub fn remove(&mut self, id: ClaimID) -> Option<Claim> {
+        let (_, rect) = self.claims.get(&id)?;
+        let rect = *rect;
+        self.contested_area -= self.newly_contested(&rect, id);
+        if number_of_cells(&rect) > MAX_CELLS {
+            self.oversized.retain(|&other| other != id);
+        } else {
+            for cell in cells(&rect) {
+                let ids = self.cells.get_mut(&cell).unwrap();
+                ids.retain(|&other| other != id);
+                if ids.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+        self.claims.remove(&id).map(|(claim, _)| claim)
+    }
+
+    /// The claims that might overlap `rect`, going by the cells they're in.
+    fn candidates(&self, rect: &Rect) -> BTreeSet<ClaimID> {
+        let mut candidates: BTreeSet<_> =
+            self.oversized.iter().copied().collect();
+        if number_of_cells(rect) > self.cells.len() as u64 {
+            candidates.extend(self.cells.values().flatten());
+        } else {
+            for cell in cells(rect) {
+                candidates.extend(self.cells.get(&cell).into_iter().flatten());
+            }
+        }
+        candidates
+    }
+
+    /// The IDs of the claims that cover `position`, in order.
+    pub fn covering(&self, position: &Position) -> Vec<ClaimID> {
+        let one_inch = Dimensions {
+            width: 1,
+            height: 1,
+        };
+        let square = Rect::new(position, &one_inch).unwrap();
+        self.intersecting(&square)
+    }
+
+    /// The IDs of the claims that overlap `rect`, in order.
+    pub fn intersecting(&self, rect: &Rect) -> Vec<ClaimID> {
+        self.candidates(rect)
+            .into_iter()
+            .filter(|id| self.claims[id].1.overlaps(rect))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{random, EXAMPLE, HUGE_CLAIMS};
+    use crate::{overlaps, Day03};
+    use aoc_core::Solution;
+
+    #[test]
+    fn queries() {
+        let mut index = ClaimIndex::new();
+        for claim in Day03::parse(EXAMPLE).unwrap() {
+            index.insert(claim).unwrap();
+        }
+        assert_eq!(index.contested_area(), 4);
+        assert_eq!(
+            index.covering(&Position { x: 3, y: 3 }),
+            vec![ClaimID(1), ClaimID(2)]
+        );
+        assert_eq!(index.covering(&Position { x: 0, y: 0 }), vec![]);
+        let rect = Rect::new(
+            &Position { x: 4, y: 4 },
+            &Dimensions {
+                width: 2,
+                height: 2,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            index.intersecting(&rect),
+            vec![ClaimID(1), ClaimID(2), ClaimID(3)]
+        );
+
+        let duplicate = Day03::parse("#2 @ 0,0: 1x1").unwrap().remove(0);
+        assert_eq!(
+            index.insert(duplicate),
+            Err(ClaimError::Duplicate(ClaimID(2)))
+        );
+        assert_eq!(index.remove(ClaimID(2)).unwrap().id, ClaimID(2));
+        assert_eq!(index.remove(ClaimID(2)), None);
+        assert_eq!(index.contested_area(), 0);
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn tracks_contested_area() {
+        let mut random = random(54321);
+        let input: String = (1..=80)
+            .map(|id| {
+                // Some claims big enough to be kept out of the cells
+                let size = if id % 20 == 0 { 3000 } else { 150 };
+                format!(
+                    "#{} @ {},{}: {}x{}\n",
+                    id,
+                    random(500),
+                    random(500),
+                    random(size) + 1,
+                    random(size) + 1
+                )
+            })
+            .collect();
+        let claims = Day03::parse(&input).unwrap();
+
+        let mut index = ClaimIndex::new();
+        let mut present = Vec::new();
+        for (step, claim) in claims.iter().enumerate() {
+            index.insert(claim.clone()).unwrap();
+            present.push(claim.clone());
+            if step % 3 == 2 {
+                let removed =
+                    present.remove(random(present.len() as u32) as usize);
+                assert_eq!(index.remove(removed.id), Some(removed));
+            }
+            assert_eq!(
+                index.contested_area(),
+                overlaps(&present).contested_area
+            );
+        }
+        let position = Position { x: 250, y: 250 };
+        let mut covering: Vec<_> = present
+            .iter()
+            .filter(|claim| Rect::try_from(*claim).unwrap().contains(&position))
+            .map(|claim| claim.id)
+            .collect();
+        covering.sort();
+        assert_eq!(index.covering(&position), covering);
+    }
+
+    #[test]
+    fn huge_claims() {
+        let mut index = ClaimIndex::new();
+        for claim in Day03::parse(HUGE_CLAIMS).unwrap() {
+            index.insert(claim).unwrap();
+        }
+        let side = u32::MAX as u128;
+        assert_eq!(index.contested_area(), 2 * side * side);
+        index.remove(ClaimID(4));
+        assert_eq!(index.contested_area(), side * side);
+    }
+}
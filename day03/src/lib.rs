@@ -0,0 +1,163 @@
+use aoc_core::{Result, Solution};
+use aoc_parse::{number, parse_lines, Parse, Res};
+use nom::{
+    bytes::complete::tag,
+    combinator::map,
+    error::context,
+    sequence::{preceded, separated_pair, tuple},
+};
+use std::cmp::Eq;
+use std::fmt;
+
+mod index;
+mod overlap;
+mod rect;
+mod render;
+
+pub use index::ClaimIndex;
+pub use overlap::{overlaps, Overlaps};
+pub use rect::{validate, ClaimError, Rect, FABRIC_SIZE};
+pub use render::{Heatmap, MAX_SQUARES};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Claim {
+    pub id: ClaimID,
+    pub position: Position,
+    pub dimensions: Dimensions,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct ClaimID(pub u32);
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct Position {
+    pub x: u32,
+    pub y: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Dimensions {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl fmt::Display for ClaimID {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Parse for ClaimID {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context("ClaimID", map(preceded(tag("#"), number), ClaimID))(input)
+    }
+}
+
+impl Parse for Position {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "Position",
+            map(separated_pair(number, tag(","), number), |(x, y)| {
+                Position { x, y }
+            }),
+        )(input)
+    }
+}
+
+impl Parse for Dimensions {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "Dimensions",
+            map(
+                separated_pair(number, tag("x"), number),
+                |(width, height)| Dimensions { width, height },
+            ),
+        )(input)
+    }
+}
+
+impl Parse for Claim {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "Claim",
+            map(
+                tuple((
+                    ClaimID::nom_parse,
+                    tag(" @ "),
+                    Position::nom_parse,
+                    tag(": "),
+                    Dimensions::nom_parse,
+                )),
+                |(id, _, position, _, dimensions)| Claim {
+                    id,
+                    position,
+                    dimensions,
+                },
+            ),
+        )(input)
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Claim>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_lines(input)?)
+    }
+
+    fn part1(claims: &Self::Input) -> Result<String> {
+        Ok(overlaps(claims).contested_area.to_string())
+    }
+
+    fn part2(claims: &Self::Input) -> Result<String> {
+        let intact: Vec<_> = overlaps(claims).intact().collect();
+        match intact[..] {
+            [index] => Ok(claims[index].id.to_string()),
+            _ => Err(format!(
+                "expected exactly one intact claim, found {}",
+                intact.len()
+            )
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(crate) const EXAMPLE: &str =
+        "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+
+    /// A small linear congruential generator, so that randomised tests get
+    /// the same numbers every time. Each call gives a number below `bound`.
+    pub(crate) fn random(mut seed: u32) -> impl FnMut(u32) -> u32 {
+        move |bound| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) % bound
+        }
+    }
+
+    /// Four claims as big as they can be, in two overlapping pairs, whose
+    /// contested area is too big for a `u64`.
+    pub(crate) const HUGE_CLAIMS: &str = "\
+#1 @ 0,0: 4294967295x4294967295
+#2 @ 0,0: 4294967295x4294967295
+#3 @ 0,4294967295: 4294967295x4294967295
+#4 @ 0,4294967295: 4294967295x4294967295
+";
+
+    #[test]
+    fn part1_example() {
+        let claims = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(Day03::part1(&claims).unwrap(), "4");
+    }
+
+    #[test]
+    fn part2_example() {
+        let claims = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(Day03::part2(&claims).unwrap(), "3");
+    }
+}
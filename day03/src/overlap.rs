@@ -0,0 +1,351 @@
+use crate::{Claim, Rect};
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::ops::Range;
+
+/// How a set of claims overlap each other.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Overlaps {
+    /// The number of square inches covered by two or more claims. Claims can
+    /// be up to `2^32` inches on a side, so this can be more than a `u64`
+    /// holds.
+    pub contested_area: u128,
+    /// For each claim, by its index in the input, the indices of the claims
+    /// it overlaps, in order.
+    pub collisions: Vec<Vec<usize>>,
+}
+
+impl Overlaps {
+    /// The indices of the claims that don't overlap any other claim.
+    pub fn intact(&self) -> impl Iterator<Item = usize> + '_ {
+        self.collisions
+            .iter()
+            .enumerate()
+            .filter(|(_, collisions)| collisions.is_empty())
+            .map(|(index, _)| index)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Change {
+    Add(usize),
+    Remove(usize),
+}
+
+/// A segment tree over the gaps between the distinct y coordinates, tracking
+/// which claims cross the sweep line at each y. Each claim is stored on the
+/// nodes that exactly make up its range, so both updates and finding the
+/// claims that cover a point take `O(log n)`, plus the number of claims found.
+struct SweepTree {
+    ys: Vec<u64>,
+    claims: Vec<Vec<usize>>,
+    /// How much of each node's range is covered by at least one claim.
+    covered_once: Vec<u64>,
+    /// How much of each node's range is covered by at least two claims.
+    covered_twice: Vec<u64>,
+}
+
+impl SweepTree {
+    fn new(ys: Vec<u64>) -> Self {
+        let nodes = 4 * ys.len();
+        SweepTree {
+            ys,
+            claims: vec![Vec::new(); nodes],
+            covered_once: vec![0; nodes],
+            covered_twice: vec![0; nodes],
+        }
+    }
+
+    fn gap(&self, y: u64) -> usize {
+        self.ys.binary_search(&y).unwrap()
+    }
+
+    /// Adds or removes `claim` over `top..bottom`.
+    fn update(&mut self, top: u64, bottom: u64, claim: usize, add: bool) {
+        let gaps = self.gap(top)..self.gap(bottom);
+        let change = if add {
+            Change::Add(claim)
+        } else {
+            Change::Remove(claim)
+        };
+        self.update_node(1, 0..self.ys.len() - 1, &gaps, change);
+    }
+
+    fn update_node(
+        &mut self,
+        node: usize,
+        span: Range<usize>,
+        gaps: &Range<usize>,
+        change: Change,
+    ) {
+        if gaps.end <= span.start || span.end <= gaps.start {
+            return;
+        }
+        if gaps.start <= span.start && span.end <= gaps.end {
+            let claims = &mut self.claims[node];
+            match change {
+                Change::Add(claim) => claims.push(claim),
+                Change::Remove(claim) => {
+                    let index =
+                        claims.iter().position(|&c| c == claim).unwrap();
+                    claims.swap_remove(index);
+                }
+            }
+        } else {
+            let mid = (span.start + span.end) / 2;
+            self.update_node(2 * node, span.start..mid, gaps, change);
+            self.update_node(2 * node + 1, mid..span.end, gaps, change);
+        }
+        self.pull(node, span.start, span.end);
+    }
+
+    /// Recomputes how much of a node is covered from its own claims and its
+    /// children.
+    fn pull(&mut self, node: usize, lo: usize, hi: usize) {
+        let width = self.ys[hi] - self.ys[lo];
+        let is_leaf = hi - lo == 1;
+        let children = |covered: &[u64]| {
+            if is_leaf {
+                0
+            } else {
+                covered[2 * node] + covered[2 * node + 1]
+            }
+        };
+        let (once, twice) = match self.claims[node].len() {
+            0 => (children(&self.covered_once), children(&self.covered_twice)),
+            1 => (width, children(&self.covered_once)),
+            _ => (width, width),
+        };
+        self.covered_once[node] = once;
+        self.covered_twice[node] = twice;
+    }
+
+    /// The length of the sweep line covered by at least one claim.
+    fn covered_once(&self) -> u64 {
+        self.covered_once[1]
+    }
+
+    /// The length of the sweep line covered by at least two claims.
+    fn covered_twice(&self) -> u64 {
+        self.covered_twice[1]
+    }
+
+    /// The claims whose range includes `y`.
+    fn covering(&self, y: u64) -> Vec<usize> {
+        let gap = self.gap(y);
+        let (mut node, mut lo, mut hi) = (1, 0, self.ys.len() - 1);
+        let mut covering = self.claims[node].clone();
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if gap < mid {
+                node *= 2;
+                hi = mid;
+            } else {
+                node = 2 * node + 1;
+                lo = mid;
+            }
+            covering.extend_from_slice(&self.claims[node]);
+        }
+        covering
+    }
+}
+
+/// The points at which each rectangle joins and leaves a sweep line moving
+/// from left to right, and the distinct y coordinates of their edges.
+///
+/// Rectangles leave the sweep line before others join it at the same x, so
+/// rectangles that only touch edges don't overlap.
+fn sweep_events(rects: &[Option<Rect>]) -> (Vec<(u64, bool, usize)>, Vec<u64>) {
+    let mut events = Vec::new();
+    for (index, rect) in rects.iter().enumerate() {
+        if let Some(rect) = rect {
+            events.push((rect.left(), true, index));
+            events.push((rect.right(), false, index));
+        }
+    }
+    events.sort_unstable();
+    let mut ys: Vec<u64> = rects
+        .iter()
+        .flatten()
+        .flat_map(|rect| vec![rect.top(), rect.bottom()])
+        .collect();
+    ys.sort_unstable();
+    ys.dedup();
+    (events, ys)
+}
+
+/// The area covered by at least one of the rectangles, and the area covered
+/// by at least two.
+pub(crate) fn coverage(rects: &[Rect]) -> (u128, u128) {
+    let rects: Vec<_> = rects.iter().copied().map(Some).collect();
+    let (events, ys) = sweep_events(&rects);
+    let (mut once, mut twice) = (0, 0);
+    let mut last_x = match events.first() {
+        Some(&(x, _, _)) => x,
+        None => return (once, twice),
+    };
+    let mut tree = SweepTree::new(ys);
+    for (x, joining, index) in events {
+        let strip = (x - last_x) as u128;
+        once += tree.covered_once() as u128 * strip;
+        twice += tree.covered_twice() as u128 * strip;
+        last_x = x;
+        let rect = rects[index].unwrap();
+        tree.update(rect.top(), rect.bottom(), index, joining);
+    }
+    (once, twice)
+}
+
+/// Finds how the claims overlap by sweeping a line across the fabric from
+/// left to right, taking `O(n log n)` time plus the number of collisions.
+/// Empty claims don't cover any fabric, so never overlap anything.
+pub fn overlaps(claims: &[Claim]) -> Overlaps {
+    let mut collisions = vec![Vec::new(); claims.len()];
+    let rects: Vec<_> = claims
+        .iter()
+        .map(|claim| Rect::try_from(claim).ok())
+        .collect();
+
+    let (events, ys) = sweep_events(&rects);
+
+    let mut contested_area = 0;
+    if events.is_empty() {
+        return Overlaps {
+            contested_area,
+            collisions,
+        };
+    }
+    let mut tree = SweepTree::new(ys);
+    // The claims crossing the sweep line, by the top of their range
+    let mut by_top = BTreeSet::new();
+    let mut last_x = events[0].0;
+    for (x, joining, index) in events {
+        contested_area += tree.covered_twice() as u128 * (x - last_x) as u128;
+        last_x = x;
+
+        let rect = rects[index].unwrap();
+        let (top, bottom) = (rect.top(), rect.bottom());
+        if !joining {
+            tree.update(top, bottom, index, false);
+            by_top.remove(&(top, index));
+            continue;
+        }
+        // Two ranges overlap exactly when one starts inside the other
+        let starting_inside =
+            by_top.range((top, 0)..(bottom, 0)).map(|&(_, other)| other);
+        let containing_top = tree
+            .covering(top)
+            .into_iter()
+            .filter(|&other| rects[other].unwrap().top() < top);
+        for other in starting_inside.chain(containing_top) {
+            collisions[index].push(other);
+            collisions[other].push(index);
+        }
+        tree.update(top, bottom, index, true);
+        by_top.insert((top, index));
+    }
+
+    for collisions in collisions.iter_mut() {
+        collisions.sort_unstable();
+    }
+    Overlaps {
+        contested_area,
+        collisions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{random, HUGE_CLAIMS};
+    use crate::Day03;
+    use aoc_core::Solution;
+
+    #[test]
+    fn collisions() {
+        let claims = Day03::parse(
+            "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n#4 @ 0,0: 3x3\n\
+             #5 @ 2,2: 1x1\n#6 @ 9,9: 0x4\n",
+        )
+        .unwrap();
+        let overlaps = overlaps(&claims);
+        // #4 only touches the edges of #1 and #2
+        assert_eq!(
+            overlaps.collisions,
+            vec![vec![1], vec![0], vec![], vec![4], vec![3], vec![]]
+        );
+        assert_eq!(overlaps.contested_area, 5);
+        assert_eq!(overlaps.intact().collect::<Vec<_>>(), vec![2, 5]);
+    }
+
+    #[test]
+    fn huge_claims() {
+        // Claims past the edge of any fabric don't overflow, and empty ones
+        // overlap nothing
+        let claims = Day03::parse(
+            "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 4294967295,0: 4294967295x1\n\
+             #4 @ 5,5: 0x2\n#5 @ 998,998: 2x2\n#6 @ 998,999: 2x2\n",
+        )
+        .unwrap();
+        let found = overlaps(&claims);
+        assert_eq!(found.contested_area, 4 + 2);
+        assert_eq!(found.intact().collect::<Vec<_>>(), vec![2, 3]);
+
+        let claims = Day03::parse(HUGE_CLAIMS).unwrap();
+        let side = u32::MAX as u128;
+        assert_eq!(overlaps(&claims).contested_area, 2 * side * side);
+        assert_eq!(
+            Day03::part1(&claims).unwrap(),
+            (2 * side * side).to_string()
+        );
+    }
+
+    #[test]
+    fn matches_counting_every_square() {
+        let mut random = random(12345);
+        let input: String = (1..=60)
+            .map(|id| {
+                format!(
+                    "#{} @ {},{}: {}x{}\n",
+                    id,
+                    random(30),
+                    random(30),
+                    random(10) + 1,
+                    random(10) + 1
+                )
+            })
+            .collect();
+        let claims = Day03::parse(&input).unwrap();
+
+        let mut counts = vec![vec![Vec::new(); 40]; 40];
+        for (index, claim) in claims.iter().enumerate() {
+            let Claim {
+                position,
+                dimensions,
+                ..
+            } = claim;
+            for x in position.x..position.x + dimensions.width {
+                for y in position.y..position.y + dimensions.height {
+                    counts[x as usize][y as usize].push(index);
+                }
+            }
+        }
+        let mut collisions = vec![Vec::new(); claims.len()];
+        for square in counts.iter().flatten() {
+            for &claim in square {
+                collisions[claim]
+                    .extend(square.iter().filter(|&&c| c != claim));
+            }
+        }
+        for collisions in collisions.iter_mut() {
+            collisions.sort_unstable();
+            collisions.dedup();
+        }
+        let contested =
+            counts.iter().flatten().filter(|s| s.len() >= 2).count();
+
+        let overlaps = overlaps(&claims);
+        assert_eq!(overlaps.contested_area, contested as u128);
+        assert_eq!(overlaps.collisions, collisions);
+    }
+}
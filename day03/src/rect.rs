@@ -0,0 +1,181 @@
+use crate::{Claim, ClaimID, Dimensions, Position};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+/// The fabric from the puzzle, which is at least this big.
+pub const FABRIC_SIZE: Dimensions = Dimensions {
+    width: 1000,
+    height: 1000,
+};
+
+/// A rectangle of at least one square inch, covering `left..right` across
+/// and `top..bottom` down. The edges are stored as `u64`, so the far edge of
+/// any claim fits without overflowing.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Rect {
+    left: u64,
+    right: u64,
+    top: u64,
+    bottom: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ClaimError {
+    /// The claim has no width or no height, so covers no fabric.
+    Empty(ClaimID),
+    /// Part of the claim is past the edge of the fabric.
+    OutsideFabric(ClaimID),
+    /// There's already a claim with the same ID.
+    Duplicate(ClaimID),
+}
+
+impl fmt::Display for ClaimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClaimError::Empty(id) => write!(f, "claim #{} is empty", id),
+            ClaimError::OutsideFabric(id) => {
+                write!(f, "claim #{} goes past the edge of the fabric", id)
+            }
+            ClaimError::Duplicate(id) => {
+                write!(f, "there's already a claim #{}", id)
+            }
+        }
+    }
+}
+
+impl Error for ClaimError {}
+
+impl Rect {
+    /// The rectangle with its top left corner at `position`, or `None` if
+    /// it would be empty.
+    pub fn new(position: &Position, dimensions: &Dimensions) -> Option<Self> {
+        if dimensions.width == 0 || dimensions.height == 0 {
+            return None;
+        }
+        let (left, top) = (position.x as u64, position.y as u64);
+        Some(Rect {
+            left,
+            right: left + dimensions.width as u64,
+            top,
+            bottom: top + dimensions.height as u64,
+        })
+    }
+
+    pub fn left(&self) -> u64 {
+        self.left
+    }
+
+    /// Just past the rightmost column of the rectangle.
+    pub fn right(&self) -> u64 {
+        self.right
+    }
+
+    pub fn top(&self) -> u64 {
+        self.top
+    }
+
+    /// Just past the bottom row of the rectangle.
+    pub fn bottom(&self) -> u64 {
+        self.bottom
+    }
+
+    /// The number of square inches covered, which can be more than a `u64`
+    /// holds.
+    pub fn area(&self) -> u128 {
+        (self.right - self.left) as u128 * (self.bottom - self.top) as u128
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        let (x, y) = (position.x as u64, position.y as u64);
+        self.left <= x && x < self.right && self.top <= y && y < self.bottom
+    }
+
+    /// The part of the fabric both rectangles cover, if any.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let rect = Rect {
+            left: self.left.max(other.left),
+            right: self.right.min(other.right),
+            top: self.top.max(other.top),
+            bottom: self.bottom.min(other.bottom),
+        };
+        if rect.left < rect.right && rect.top < rect.bottom {
+            Some(rect)
+        } else {
+            None
+        }
+    }
+
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Whether the rectangle fits on a piece of fabric of size `fabric`.
+    pub fn fits(&self, fabric: &Dimensions) -> bool {
+        self.right <= fabric.width as u64 && self.bottom <= fabric.height as u64
+    }
+}
+
+impl TryFrom<&Claim> for Rect {
+    type Error = ClaimError;
+
+    fn try_from(claim: &Claim) -> Result<Self, Self::Error> {
+        Rect::new(&claim.position, &claim.dimensions)
+            .ok_or(ClaimError::Empty(claim.id))
+    }
+}
+
+/// Every claim that's empty or doesn't fit on a piece of fabric of size
+/// `fabric`, in order.
+pub fn validate(claims: &[Claim], fabric: &Dimensions) -> Vec<ClaimError> {
+    claims
+        .iter()
+        .filter_map(|claim| match Rect::try_from(claim) {
+            Ok(rect) if rect.fits(fabric) => None,
+            Ok(_) => Some(ClaimError::OutsideFabric(claim.id)),
+            Err(err) => Some(err),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::HUGE_CLAIMS;
+    use crate::Day03;
+    use aoc_core::Solution;
+
+    #[test]
+    fn rects() {
+        let claims = Day03::parse(
+            "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 4294967295,0: 4294967295x1\n\
+             #4 @ 5,5: 0x2\n#5 @ 998,998: 2x2\n#6 @ 998,999: 2x2\n",
+        )
+        .unwrap();
+        let rect = |index: usize| Rect::try_from(&claims[index]);
+        let intersection = rect(0).unwrap().intersection(&rect(1).unwrap());
+        assert_eq!(intersection.map(|rect| rect.area()), Some(4));
+        assert!(intersection.unwrap().contains(&Position { x: 4, y: 4 }));
+        assert!(!intersection.unwrap().contains(&Position { x: 5, y: 4 }));
+        assert_eq!(rect(2).unwrap().right(), 2 * u32::MAX as u64);
+        assert_eq!(rect(3), Err(ClaimError::Empty(ClaimID(4))));
+        assert_eq!(
+            validate(&claims, &FABRIC_SIZE),
+            vec![
+                ClaimError::OutsideFabric(ClaimID(3)),
+                ClaimError::Empty(ClaimID(4)),
+                ClaimError::OutsideFabric(ClaimID(6)),
+            ]
+        );
+
+        // Areas bigger than a u64 can hold
+        let claims = Day03::parse(HUGE_CLAIMS).unwrap();
+        let side = u32::MAX as u128;
+        let (top, bottom) = (
+            Rect::try_from(&claims[0]).unwrap(),
+            Rect::try_from(&claims[2]).unwrap(),
+        );
+        assert_eq!(top.area(), side * side);
+        assert_eq!(top.intersection(&bottom), None);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{overlaps, Claim, Position, Rect};
+use aoc_core::Result;
+use std::convert::TryFrom;
+use std::io::Write;
+
+/// The most square inches a heatmap will hold, which is a piece of fabric
+/// 4096 inches on a side.
+pub const MAX_SQUARES: u64 = 4096 * 4096;
+
+/// How many claims cover each square inch of the fabric, for drawing.
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    width: u32,
+    height: u32,
+    counts: Vec<u32>,
+    /// Whether each square belongs to a claim that overlaps no other.
+    intact: Vec<bool>,
+}
+
+impl Heatmap {
+    /// Lays out the claims on a piece of fabric just big enough to hold
+    /// them all. Fails if that would be more than `MAX_SQUARES` square
+    /// inches.
+    pub fn new(claims: &[Claim]) -> Result<Self> {
+        const TOO_BIG: &str = "the claims are too big to draw";
+        let rects: Vec<_> = claims
+            .iter()
+            .map(|claim| Rect::try_from(claim).ok())
+            .collect();
+        let size = |edge: fn(&Rect) -> u64| {
+            rects.iter().flatten().map(edge).max().unwrap_or(0)
+        };
+        let (width, height) = (size(Rect::right), size(Rect::bottom));
+        let squares = width
+            .checked_mul(height)
+            .filter(|&squares| squares <= MAX_SQUARES)
+            .ok_or(TOO_BIG)?;
+        let width = u32::try_from(width).map_err(|_| TOO_BIG)?;
+        let height = u32::try_from(height).map_err(|_| TOO_BIG)?;
+        let squares = squares as usize;
+        let mut heatmap = Heatmap {
+            width,
+            height,
+            counts: vec![0; squares],
+            intact: vec![false; squares],
+        };
+
+        let intact: Vec<_> = overlaps(claims).intact().collect();
+        for (index, rect) in rects.iter().enumerate() {
+            if let Some(rect) = rect {
+                let is_intact = intact.contains(&index);
+                for y in rect.top()..rect.bottom() {
+                    for x in rect.left()..rect.right() {
+                        let square = heatmap.square(x as u32, y as u32);
+                        heatmap.counts[square] += 1;
+                        heatmap.intact[square] = is_intact;
+                    }
+                }
+            }
+        }
+        Ok(heatmap)
+    }
+
+    fn square(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// How many claims cover `position`.
+    pub fn count(&self, position: &Position) -> u32 {
+        if position.x < self.width && position.y < self.height {
+            self.counts[self.square(position.x, position.y)]
+        } else {
+            0
+        }
+    }
+
+    /// Draws the fabric as text, one character per square inch: `.` where
+    /// there are no claims, `#` for an intact claim, and otherwise the
+    /// number of claims covering it, or `+` for ten or more. Only meant for
+    /// small inputs, like the puzzle's examples.
+    pub fn to_ascii(&self) -> String {
+        let mut ascii = String::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let square = self.square(x, y);
+                ascii.push(match self.counts[square] {
+                    _ if self.intact[square] => '#',
+                    0 => '.',
+                    count @ 1..=9 => (b'0' + count as u8) as char,
+                    _ => '+',
+                });
+            }
+            ascii.push('\n');
+        }
+        ascii
+    }
+
+    /// Writes the fabric as a PNG, one pixel per square inch, shading from
+    /// blue for one claim to red for the most contested squares, with intact
+    /// claims in green.
+    pub fn write_png<W: Write>(&self, writer: W) -> Result<()> {
+        let max_count = self.counts.iter().copied().max().unwrap_or(0);
+        let mut pixels = Vec::with_capacity(self.counts.len() * 3);
+        for (&count, &intact) in self.counts.iter().zip(self.intact.iter()) {
+            let pixel = if intact {
+                [40, 220, 60]
+            } else if count == 0 {
+                [16, 16, 16]
+            } else if max_count == 1 {
+                [40, 60, 200]
+            } else {
+                // How contested the square is, from 0 for a single claim to
+                // 1 for the most claims on any square
+                let heat = (count - 1) as f64 / (max_count - 1) as f64;
+                [
+                    (40.0 + 215.0 * heat) as u8,
+                    60,
+                    (200.0 * (1.0 - heat)) as u8,
+                ]
+            };
+            pixels.extend_from_slice(&pixel);
+        }
+
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::EXAMPLE;
+    use crate::Day03;
+    use aoc_core::Solution;
+
+    #[test]
+    fn ascii_example() {
+        let claims = Day03::parse(EXAMPLE).unwrap();
+        let heatmap = Heatmap::new(&claims).unwrap();
+        assert_eq!(
+            heatmap.to_ascii(),
+            ".......\n\
+             ...1111\n\
+             ...1111\n\
+             .112211\n\
+             .112211\n\
+             .1111##\n\
+             .1111##\n"
+        );
+        assert_eq!(heatmap.count(&Position { x: 3, y: 3 }), 2);
+        assert_eq!(heatmap.count(&Position { x: 50, y: 0 }), 0);
+
+        let mut png = Vec::new();
+        heatmap.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn huge_heatmaps_are_refused() {
+        let claims = Day03::parse("#1 @ 0,0: 200000x200000\n").unwrap();
+        let err = Heatmap::new(&claims).unwrap_err();
+        assert_eq!(err.to_string(), "the claims are too big to draw");
+
+        let claims = Day03::parse("#1 @ 4095,0: 1x4096\n").unwrap();
+        let heatmap = Heatmap::new(&claims).unwrap();
+        assert_eq!(
+            heatmap.width() as u64 * heatmap.height() as u64,
+            MAX_SQUARES
+        );
+        let claims = Day03::parse("#1 @ 4096,0: 1x4096\n").unwrap();
+        assert!(Heatmap::new(&claims).is_err());
+    }
+}
@@ -0,0 +1,18 @@
+//! Prints how each guard in a log slept, e.g.
+//! `cargo run -p day04 --example report -- day04/input`.
+
+use aoc_core::{Result, Solution};
+use day04::{Day04, SleepReport};
+use std::env;
+use std::fs;
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let input = match &args[..] {
+        [input] => input,
+        _ => return Err("usage: report INPUT".into()),
+    };
+    let events = Day04::parse(&fs::read_to_string(input)?)?;
+    println!("{}", SleepReport::new(&events)?);
+    Ok(())
+}
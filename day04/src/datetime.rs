@@ -0,0 +1,277 @@
+use aoc_parse::{number, Parse, Res};
+use nom::{
+    bytes::complete::tag,
+    combinator::{map, map_res},
+    error::context,
+    sequence::{delimited, separated_pair, tuple},
+};
+use std::error::Error;
+use std::fmt;
+
+/// A day in the proleptic Gregorian calendar. Only valid dates can be
+/// constructed, so the derived ordering is chronological.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash)]
+pub struct Date {
+    year: u32,
+    month: u32,
+    day: u32,
+}
+
+/// A time of day, to the minute.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash)]
+pub struct Time {
+    hour: u32,
+    minute: u32,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash)]
+pub struct DateTime {
+    date: Date,
+    time: Time,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DateTimeError {
+    Month(u32),
+    Day {
+        year: u32,
+        month: u32,
+        day: u32,
+    },
+    Hour(u32),
+    Minute(u32),
+    /// The result of some arithmetic is before year 0 or too far in the
+    /// future to represent.
+    OutOfRange,
+    /// Two times are too far apart to count the minutes between them.
+    TooManyMinutes,
+}
+
+impl fmt::Display for DateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateTimeError::Month(month) => write!(f, "no month {}", month),
+            DateTimeError::Day { year, month, day } => {
+                write!(f, "{:04}-{:02} has no day {}", year, month, day)
+            }
+            DateTimeError::Hour(hour) => write!(f, "no hour {}", hour),
+            DateTimeError::Minute(minute) => write!(f, "no minute {}", minute),
+            DateTimeError::OutOfRange => write!(f, "date out of range"),
+            DateTimeError::TooManyMinutes => {
+                write!(f, "too many minutes apart to count")
+            }
+        }
+    }
+}
+
+impl Error for DateTimeError {}
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+fn is_leap_year(year: u32) -> bool {
+    year.is_multiple_of(4)
+        && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    pub fn new(year: u32, month: u32, day: u32) -> Result<Self, DateTimeError> {
+        if !(1..=12).contains(&month) {
+            return Err(DateTimeError::Month(month));
+        }
+        if !(1..=days_in_month(year, month)).contains(&day) {
+            return Err(DateTimeError::Day { year, month, day });
+        }
+        Ok(Date { year, month, day })
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Days since 1970-01-01, counting years from March so that leap days
+    /// come at the end of the year.
+    fn days_since_epoch(&self) -> i64 {
+        let (month, day) = (self.month as i64, self.day as i64);
+        let year = self.year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4
+            - year_of_era / 100
+            + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The inverse of `days_since_epoch`.
+    fn from_days_since_epoch(days: i64) -> Result<Self, DateTimeError> {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460
+            + day_of_era / 36_524
+            - day_of_era / 146_096)
+            / 365;
+        let day_of_year = day_of_era
+            - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        if year < 0 || year > u32::MAX as i64 {
+            return Err(DateTimeError::OutOfRange);
+        }
+        Ok(Date {
+            year: year as u32,
+            month: month as u32,
+            day: day as u32,
+        })
+    }
+}
+
+impl Time {
+    pub fn new(hour: u32, minute: u32) -> Result<Self, DateTimeError> {
+        if hour >= 24 {
+            return Err(DateTimeError::Hour(hour));
+        }
+        if minute >= 60 {
+            return Err(DateTimeError::Minute(minute));
+        }
+        Ok(Time { hour, minute })
+    }
+
+    pub fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.minute
+    }
+
+    /// Minutes since midnight.
+    pub fn minute_of_day(&self) -> u32 {
+        self.minute + 60 * self.hour
+    }
+}
+
+impl DateTime {
+    pub fn new(date: Date, time: Time) -> Self {
+        DateTime { date, time }
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    fn minutes_since_epoch(&self) -> i64 {
+        self.date.days_since_epoch() * MINUTES_PER_DAY
+            + self.time.minute_of_day() as i64
+    }
+
+    /// How many minutes it is from `self` until `later`, which is negative
+    /// if `later` is actually earlier.
+    pub fn minutes_until(&self, later: &DateTime) -> i64 {
+        later.minutes_since_epoch() - self.minutes_since_epoch()
+    }
+
+    /// The time `minutes` minutes later, or earlier if it's negative.
+    pub fn add_minutes(&self, minutes: i64) -> Result<Self, DateTimeError> {
+        let total = self
+            .minutes_since_epoch()
+            .checked_add(minutes)
+            .ok_or(DateTimeError::OutOfRange)?;
+        let minute_of_day = total.rem_euclid(MINUTES_PER_DAY) as u32;
+        Ok(DateTime {
+            date: Date::from_days_since_epoch(
+                total.div_euclid(MINUTES_PER_DAY),
+            )?,
+            time: Time {
+                hour: minute_of_day / 60,
+                minute: minute_of_day % 60,
+            },
+        })
+    }
+
+    /// Every minute from `self` up to, but not including, `end`. Empty if
+    /// `end` isn't later than `self`.
+    pub fn minutes_to(&self, end: &DateTime) -> impl Iterator<Item = Self> {
+        let start = *self;
+        (0..self.minutes_until(end).max(0))
+            .filter_map(move |minutes| start.add_minutes(minutes).ok())
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let DateTime { date, time } = self;
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            date.year, date.month, date.day, time.hour, time.minute
+        )
+    }
+}
+
+impl Parse for Date {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "Date",
+            map_res(
+                tuple((number, tag("-"), number, tag("-"), number)),
+                |(year, _, month, _, day)| Date::new(year, month, day),
+            ),
+        )(input)
+    }
+}
+
+impl Parse for Time {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "Time",
+            map_res(
+                separated_pair(number, tag(":"), number),
+                |(hour, minute)| Time::new(hour, minute),
+            ),
+        )(input)
+    }
+}
+
+impl Parse for DateTime {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "DateTime",
+            map(
+                delimited(
+                    tag("["),
+                    separated_pair(Date::nom_parse, tag(" "), Time::nom_parse),
+                    tag("]"),
+                ),
+                |(date, time)| DateTime { date, time },
+            ),
+        )(input)
+    }
+}
@@ -0,0 +1,395 @@
+use aoc_core::{Result, Solution};
+use aoc_parse::{number, parse_lines, Parse, Res};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{map, value},
+    error::context,
+    sequence::{delimited, preceded, separated_pair},
+};
+use std::convert::TryFrom;
+
+mod datetime;
+mod log;
+mod report;
+
+pub use datetime::{Date, DateTime, DateTimeError, Time};
+pub use log::{naps, InvalidLog, LogError, Nap};
+pub use report::{GuardSleep, SleepReport};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Event {
+    datetime: DateTime,
+    action: Action,
+    /// The line of the input the event was on.
+    line: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Copy)]
+pub struct GuardID(pub u32);
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Action {
+    StartShift(GuardID),
+    Sleep,
+    Wake,
+}
+
+impl Parse for GuardID {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context("GuardID", map(preceded(tag("#"), number), GuardID))(input)
+    }
+}
+
+impl Parse for Action {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "Action",
+            alt((
+                value(Action::Sleep, tag("falls asleep")),
+                value(Action::Wake, tag("wakes up")),
+                map(
+                    delimited(
+                        tag("Guard "),
+                        GuardID::nom_parse,
+                        tag(" begins shift"),
+                    ),
+                    Action::StartShift,
+                ),
+            )),
+        )(input)
+    }
+}
+
+impl Parse for Event {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "Event",
+            map(
+                separated_pair(
+                    DateTime::nom_parse,
+                    tag(" "),
+                    Action::nom_parse,
+                ),
+                |(datetime, action)| Event {
+                    datetime,
+                    action,
+                    line: 0,
+                },
+            ),
+        )(input)
+    }
+}
+
+/// How many minutes pass between `from` and `to`, or `None` if `to` is
+/// earlier. Fails if there are more than a `u32` can count.
+fn time_passed(
+    from: &DateTime,
+    to: &DateTime,
+) -> std::result::Result<Option<u32>, DateTimeError> {
+    let minutes = from.minutes_until(to);
+    if minutes < 0 {
+        return Ok(None);
+    }
+    u32::try_from(minutes)
+        .map(Some)
+        .map_err(|_| DateTimeError::TooManyMinutes)
+}
+
+/// The minute of the day of every minute from `from` up to `to`, which may be
+/// on a later day. Empty if `to` isn't later than `from`.
+fn all_minutes(from: &DateTime, to: &DateTime) -> impl Iterator<Item = u32> {
+    from.minutes_to(to)
+        .map(|datetime| datetime.time().minute_of_day())
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Event>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut events: Vec<Event> = parse_lines(input)?;
+        for (index, event) in events.iter_mut().enumerate() {
+            event.line = index + 1;
+        }
+        events.sort();
+        Ok(events)
+    }
+
+    fn part1(events: &Self::Input) -> Result<String> {
+        let (sleepiest_guard, sleepiest_minute) = SleepReport::new(events)?
+            .strategy1()
+            .ok_or("no guard ever fell asleep during the midnight hour")?;
+        Ok((sleepiest_guard.0 * sleepiest_minute).to_string())
+    }
+
+    fn part2(events: &Self::Input) -> Result<String> {
+        let (sleepiest_guard, sleepiest_minute) = SleepReport::new(events)?
+            .strategy2()
+            .ok_or("no guard ever fell asleep during the midnight hour")?;
+        Ok((sleepiest_guard.0 * sleepiest_minute).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+";
+
+    #[test]
+    fn part1_example() {
+        let events = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(Day04::part1(&events).unwrap(), "240");
+    }
+
+    #[test]
+    fn part2_example() {
+        let events = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(Day04::part2(&events).unwrap(), "4455");
+    }
+
+    #[test]
+    fn sleep_report() {
+        let events = Day04::parse(EXAMPLE).unwrap();
+        let report = SleepReport::new(&events).unwrap();
+
+        let guard10 = report.guard(GuardID(10)).unwrap();
+        assert_eq!(guard10.shifts, 2);
+        assert_eq!(guard10.naps, 3);
+        assert_eq!(guard10.minutes_asleep, 50);
+        assert_eq!(guard10.histogram[24], 2);
+        assert_eq!(guard10.histogram[4], 0);
+        assert_eq!(guard10.sleepiest_minute(), Some((24, 2)));
+        assert_eq!(guard10.average_nap(), Some(50.0 / 3.0));
+
+        let guard99 = report.guard(GuardID(99)).unwrap();
+        assert_eq!(guard99.shifts, 3);
+        assert_eq!(guard99.minutes_asleep, 30);
+        assert_eq!(guard99.sleepiest_minute(), Some((45, 3)));
+        assert_eq!(report.guard(GuardID(1)), None);
+
+        assert_eq!(report.strategy1(), Some((GuardID(10), 24)));
+        assert_eq!(report.strategy2(), Some((GuardID(99), 45)));
+        assert_eq!(
+            report.to_string(),
+            "\
+Guard  Shifts  Asleep  Naps  Avg nap  Sleepiest minute
+   10       2      50     3     16.7  00:24 (2 times)
+   99       3      30     3     10.0  00:45 (3 times)"
+        );
+
+        // A guard who never sleeps is still in the report
+        let events = Day04::parse(
+            "[1518-11-01 00:00] Guard #10 begins shift\n\
+             [1518-11-02 00:00] Guard #99 begins shift\n\
+             [1518-11-02 00:05] falls asleep\n\
+             [1518-11-02 00:06] wakes up\n",
+        )
+        .unwrap();
+        let report = SleepReport::new(&events).unwrap();
+        let guard10 = report.guard(GuardID(10)).unwrap();
+        assert_eq!(guard10.sleepiest_minute(), None);
+        assert_eq!(guard10.average_nap(), None);
+        assert_eq!(report.guards().count(), 2);
+        assert_eq!(report.strategy1(), Some((GuardID(99), 5)));
+        assert!(report.to_string().contains("\n   10       1       0     0"));
+
+        // Naps spanning several days cover the midnight hour each day, and
+        // naps spanning thousands of years are counted without stepping
+        // through them
+        let events = Day04::parse(
+            "[1518-11-01 00:00] Guard #10 begins shift\n\
+             [1518-11-01 00:30] falls asleep\n\
+             [1518-11-03 00:10] wakes up\n\
+             [1518-11-04 00:00] Guard #99 begins shift\n\
+             [1518-11-04 23:30] falls asleep\n\
+             [9518-11-04 23:30] wakes up\n",
+        )
+        .unwrap();
+        let report = SleepReport::new(&events).unwrap();
+        let guard10 = report.guard(GuardID(10)).unwrap();
+        assert_eq!(guard10.minutes_asleep, 2 * 24 * 60 - 20);
+        assert_eq!(guard10.histogram[9], 2);
+        assert_eq!(guard10.histogram[10], 1);
+        assert_eq!(guard10.histogram[30], 2);
+        assert_eq!(guard10.sleepiest_minute(), Some((0, 2)));
+        let guard99 = report.guard(GuardID(99)).unwrap();
+        let days = DateTime::new(
+            Date::new(1518, 11, 4).unwrap(),
+            Time::new(0, 0).unwrap(),
+        )
+        .minutes_until(&DateTime::new(
+            Date::new(9518, 11, 4).unwrap(),
+            Time::new(0, 0).unwrap(),
+        )) / (24 * 60);
+        assert_eq!(guard99.minutes_asleep as i64, days * 24 * 60);
+        assert!(guard99.histogram.iter().all(|&times| times as i64 == days));
+
+        // Too long to count at all
+        let events = Day04::parse(
+            "[1518-11-01 00:00] Guard #10 begins shift\n\
+             [1518-11-01 00:30] falls asleep\n\
+             [9999-11-03 00:10] wakes up\n",
+        )
+        .unwrap();
+        let err = SleepReport::new(&events).unwrap_err();
+        assert_eq!(err.to_string(), "too many minutes apart to count");
+    }
+
+    #[test]
+    fn datetime_arithmetic() {
+        let datetime = |date: (u32, u32, u32), time: (u32, u32)| {
+            DateTime::new(
+                Date::new(date.0, date.1, date.2).unwrap(),
+                Time::new(time.0, time.1).unwrap(),
+            )
+        };
+        let before_midnight = datetime((1518, 11, 1), (23, 58));
+        let after_midnight = datetime((1518, 11, 2), (0, 3));
+        assert_eq!(before_midnight.minutes_until(&after_midnight), 5);
+        assert_eq!(after_midnight.minutes_until(&before_midnight), -5);
+        assert_eq!(
+            all_minutes(&before_midnight, &after_midnight).collect::<Vec<_>>(),
+            vec![1438, 1439, 0, 1, 2]
+        );
+        assert_eq!(time_passed(&after_midnight, &before_midnight), Ok(None));
+        assert_eq!(time_passed(&before_midnight, &after_midnight), Ok(Some(5)));
+        // 9000 years is more minutes than a u32 holds
+        let long_nap = Nap {
+            guard: GuardID(10),
+            start: before_midnight,
+            end: datetime((10518, 11, 1), (0, 0)),
+        };
+        assert_eq!(
+            time_passed(&long_nap.start, &long_nap.end),
+            Err(DateTimeError::TooManyMinutes)
+        );
+        assert_eq!(long_nap.length(), Err(DateTimeError::TooManyMinutes));
+        assert_eq!(all_minutes(&after_midnight, &before_midnight).count(), 0);
+
+        // 1900 isn't a leap year, but 2000 and 1516 are
+        let end_of_feb = |year| datetime((year, 2, 28), (12, 0));
+        assert_eq!(
+            end_of_feb(1900).add_minutes(24 * 60).unwrap(),
+            datetime((1900, 3, 1), (12, 0))
+        );
+        assert_eq!(
+            end_of_feb(2000).add_minutes(24 * 60).unwrap(),
+            datetime((2000, 2, 29), (12, 0))
+        );
+        let new_year = datetime((1518, 12, 31), (23, 59));
+        assert_eq!(
+            new_year.add_minutes(1).unwrap(),
+            datetime((1519, 1, 1), (0, 0))
+        );
+        let leap_year = datetime((1516, 1, 1), (0, 0));
+        assert_eq!(
+            leap_year.minutes_until(&datetime((1517, 1, 1), (0, 0))),
+            366 * 24 * 60
+        );
+        assert_eq!(
+            datetime((1516, 3, 1), (0, 0)).add_minutes(-1).unwrap(),
+            datetime((1516, 2, 29), (23, 59))
+        );
+
+        // Ordering agrees with the arithmetic
+        let mut times: Vec<_> = (0..2000)
+            .map(|step| leap_year.add_minutes(step * 997 - 700_000).unwrap())
+            .collect();
+        let chronological = times.clone();
+        times.sort();
+        assert_eq!(times, chronological);
+    }
+
+    #[test]
+    fn invalid_datetimes() {
+        assert_eq!(Date::new(1518, 13, 1), Err(DateTimeError::Month(13)));
+        assert!(Date::new(1518, 2, 29).is_err());
+        assert!(Date::new(1516, 2, 29).is_ok());
+        assert!(Date::new(1518, 4, 31).is_err());
+        assert!(Date::new(1518, 4, 0).is_err());
+        assert_eq!(Time::new(24, 0), Err(DateTimeError::Hour(24)));
+        assert_eq!(Time::new(0, 60), Err(DateTimeError::Minute(60)));
+
+        let err = Day04::parse("[1518-02-30 00:00] falls asleep").unwrap_err();
+        assert!(err.to_string().contains("in Event > DateTime > Date"));
+    }
+
+    #[test]
+    fn events_are_sorted() {
+        let mut lines: Vec<_> = EXAMPLE.lines().collect();
+        lines.reverse();
+        let events = Day04::parse(&lines.join("\n")).unwrap();
+        let expected = Day04::parse(EXAMPLE).unwrap();
+        let without_lines = |events: &[Event]| {
+            events
+                .iter()
+                .map(|event| (event.datetime, event.action.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(without_lines(&events), without_lines(&expected));
+        // Each event still knows where it was in the input
+        assert_eq!(events[0].line, 17);
+    }
+
+    #[test]
+    fn invalid_logs() {
+        let events = Day04::parse(
+            "[1518-10-31 23:59] falls asleep\n\
+             [1518-11-01 00:00] Guard #10 begins shift\n\
+             [1518-11-01 00:05] falls asleep\n\
+             [1518-11-01 00:07] falls asleep\n\
+             [1518-11-01 00:25] wakes up\n\
+             [1518-11-01 00:30] wakes up\n\
+             [1518-11-01 00:40] falls asleep\n\
+             [1518-11-02 00:00] Guard #99 begins shift\n\
+             [1518-11-02 00:00] falls asleep\n",
+        )
+        .unwrap();
+        let InvalidLog(errors) = naps(&events).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                LogError::NoGuard { line: 1 },
+                LogError::DoubleSleep {
+                    line: 4,
+                    asleep_since: 3
+                },
+                LogError::WakeWithoutSleep { line: 6 },
+                LogError::ShiftEndedAsleep {
+                    guard: GuardID(10),
+                    asleep_since: 7,
+                    ended: Some(8)
+                },
+                LogError::DuplicateTimestamp { line: 9, other: 8 },
+                LogError::ShiftEndedAsleep {
+                    guard: GuardID(99),
+                    asleep_since: 9,
+                    ended: None
+                },
+            ]
+        );
+        let err = Day04::part1(&events).unwrap_err().to_string();
+        assert!(err.contains("line 6: wakes up without falling asleep"));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{
+    all_minutes, time_passed, Action, DateTime, DateTimeError, Event, GuardID,
+};
+use std::error::Error;
+use std::fmt;
+
+/// A stretch of time a guard spent asleep, from when they fell asleep up to
+/// the minute they woke.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Nap {
+    pub guard: GuardID,
+    pub start: DateTime,
+    pub end: DateTime,
+}
+
+impl Nap {
+    /// How many minutes the nap lasted. Fails if there are too many to
+    /// count.
+    pub fn length(&self) -> Result<u32, DateTimeError> {
+        Ok(time_passed(&self.start, &self.end)?.unwrap_or(0))
+    }
+
+    /// The minute of the day of each minute the guard was asleep.
+    pub fn minutes(&self) -> impl Iterator<Item = u32> {
+        all_minutes(&self.start, &self.end)
+    }
+}
+
+/// Something in the log that can't have happened. Lines are 1-indexed lines
+/// of the input.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LogError {
+    /// Someone fell asleep or woke up before any guard's shift started.
+    NoGuard { line: usize },
+    /// The guard fell asleep while already asleep.
+    DoubleSleep { line: usize, asleep_since: usize },
+    /// The guard woke up without having fallen asleep.
+    WakeWithoutSleep { line: usize },
+    /// The guard's shift ended, either because another began or because the
+    /// log did, while they were asleep.
+    ShiftEndedAsleep {
+        guard: GuardID,
+        asleep_since: usize,
+        ended: Option<usize>,
+    },
+    /// Two events happened in the same minute.
+    DuplicateTimestamp { line: usize, other: usize },
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogError::NoGuard { line } => {
+                write!(f, "line {}: no guard is on duty", line)
+            }
+            LogError::DoubleSleep { line, asleep_since } => write!(
+                f,
+                "line {}: falls asleep while already asleep since line {}",
+                line, asleep_since
+            ),
+            LogError::WakeWithoutSleep { line } => {
+                write!(f, "line {}: wakes up without falling asleep", line)
+            }
+            LogError::ShiftEndedAsleep {
+                guard,
+                asleep_since,
+                ended,
+            } => {
+                write!(
+                    f,
+                    "line {}: guard #{} fell asleep and never woke up before ",
+                    asleep_since, guard.0
+                )?;
+                match ended {
+                    Some(line) => write!(f, "the next shift on line {}", line),
+                    None => write!(f, "the end of the log"),
+                }
+            }
+            LogError::DuplicateTimestamp { line, other } => write!(
+                f,
+                "line {}: happens at the same time as line {}",
+                line, other
+            ),
+        }
+    }
+}
+
+/// Every problem found in a log, in order.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InvalidLog(pub Vec<LogError>);
+
+impl fmt::Display for InvalidLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the guard log is invalid:")?;
+        for err in self.0.iter() {
+            write!(f, "\n  {}", err)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for InvalidLog {}
+
+enum State<'a> {
+    OffDuty,
+    Awake(GuardID),
+    Asleep { guard: GuardID, since: &'a Event },
+}
+
+/// Runs through the sorted events, checking that each one makes sense given
+/// the ones before, and returns every nap they describe.
+pub fn naps(events: &[Event]) -> Result<Vec<Nap>, InvalidLog> {
+    let mut naps = Vec::new();
+    let mut errors = Vec::new();
+    let mut state = State::OffDuty;
+    let mut previous: Option<&Event> = None;
+
+    for event in events.iter() {
+        let line = event.line;
+        if let Some(previous) = previous {
+            if previous.datetime == event.datetime {
+                errors.push(LogError::DuplicateTimestamp {
+                    line,
+                    other: previous.line,
+                });
+            }
+        }
+        previous = Some(event);
+
+        state = match (&event.action, state) {
+            (&Action::StartShift(guard), state) => {
+                if let State::Asleep { guard, since } = state {
+                    errors.push(LogError::ShiftEndedAsleep {
+                        guard,
+                        asleep_since: since.line,
+                        ended: Some(line),
+                    });
+                }
+                State::Awake(guard)
+            }
+            (_, State::OffDuty) => {
+                errors.push(LogError::NoGuard { line });
+                State::OffDuty
+            }
+            (Action::Sleep, State::Awake(guard)) => State::Asleep {
+                guard,
+                since: event,
+            },
+            (Action::Sleep, State::Asleep { guard, since }) => {
+                errors.push(LogError::DoubleSleep {
+                    line,
+                    asleep_since: since.line,
+                });
+                State::Asleep { guard, since }
+            }
+            (Action::Wake, State::Awake(guard)) => {
+                errors.push(LogError::WakeWithoutSleep { line });
+                State::Awake(guard)
+            }
+            (Action::Wake, State::Asleep { guard, since }) => {
+                naps.push(Nap {
+                    guard,
+                    start: since.datetime,
+                    end: event.datetime,
+                });
+                State::Awake(guard)
+            }
+        };
+    }
+    if let State::Asleep { guard, since } = state {
+        errors.push(LogError::ShiftEndedAsleep {
+            guard,
+            asleep_since: since.line,
+            ended: None,
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(naps)
+    } else {
+        Err(InvalidLog(errors))
+    }
+}
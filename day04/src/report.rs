@@ -0,0 +1,167 @@
+use crate::{naps, Action, Event, GuardID, Nap};
+use aoc_core::Result;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// How one guard slept over all their shifts.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GuardSleep {
+    pub shifts: u32,
+    pub naps: u32,
+    pub minutes_asleep: u32,
+    /// How many times the guard was asleep during each minute of the
+    /// midnight hour.
+    pub histogram: [u32; 60],
+}
+
+impl Default for GuardSleep {
+    fn default() -> Self {
+        GuardSleep {
+            shifts: 0,
+            naps: 0,
+            minutes_asleep: 0,
+            histogram: [0; 60],
+        }
+    }
+}
+
+impl GuardSleep {
+    /// The minute past midnight the guard was most often asleep, and how
+    /// many times they were, or `None` if they never slept. Ties go to the
+    /// earliest minute.
+    pub fn sleepiest_minute(&self) -> Option<(u32, u32)> {
+        let (minute, &times) = self
+            .histogram
+            .iter()
+            .enumerate()
+            .max_by_key(|&(minute, times)| (times, -(minute as i32)))?;
+        if times == 0 {
+            return None;
+        }
+        Some((minute as u32, times))
+    }
+
+    /// The average length of the guard's naps in minutes, or `None` if they
+    /// never slept.
+    pub fn average_nap(&self) -> Option<f64> {
+        if self.naps == 0 {
+            return None;
+        }
+        Some(self.minutes_asleep as f64 / self.naps as f64)
+    }
+}
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// How many times a nap `length` minutes long covers each minute of the
+/// midnight hour, counting the days it spans rather than stepping through it
+/// a minute at a time.
+fn midnight_hour(nap: &Nap, length: u32) -> [u32; 60] {
+    let start = nap.start.time().minute_of_day() as i64;
+    let end = start + length as i64;
+    // How many of the minutes of the day `0..until`, counting on into later
+    // days, fall on `minute`
+    let times = |minute: i64, until: i64| {
+        (until - minute + MINUTES_PER_DAY - 1).div_euclid(MINUTES_PER_DAY)
+    };
+    let mut histogram = [0; 60];
+    for (minute, count) in histogram.iter_mut().enumerate() {
+        let minute = minute as i64;
+        *count = (times(minute, end) - times(minute, start)) as u32;
+    }
+    histogram
+}
+
+/// How every guard in a log slept.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SleepReport {
+    guards: BTreeMap<GuardID, GuardSleep>,
+}
+
+impl SleepReport {
+    /// Builds the report from sorted events, failing if the log doesn't make
+    /// sense or a guard slept for more minutes than can be counted.
+    pub fn new(events: &[Event]) -> Result<Self> {
+        let mut guards: BTreeMap<GuardID, GuardSleep> = BTreeMap::new();
+        for event in events.iter() {
+            if let Action::StartShift(guard) = event.action {
+                guards.entry(guard).or_default().shifts += 1;
+            }
+        }
+        for nap in naps(events)? {
+            let sleep = guards.entry(nap.guard).or_default();
+            sleep.naps += 1;
+            let length = nap.length()?;
+            sleep.minutes_asleep =
+                sleep.minutes_asleep.checked_add(length).ok_or_else(|| {
+                    format!("guard #{} slept too long to count", nap.guard.0)
+                })?;
+            // Each minute's count is at most the minutes asleep, so can't
+            // overflow either
+            let covered = midnight_hour(&nap, length);
+            for (count, times) in sleep.histogram.iter_mut().zip(&covered) {
+                *count += times;
+            }
+        }
+        Ok(SleepReport { guards })
+    }
+
+    pub fn guard(&self, guard: GuardID) -> Option<&GuardSleep> {
+        self.guards.get(&guard)
+    }
+
+    /// Every guard, in order of ID.
+    pub fn guards(&self) -> impl Iterator<Item = (GuardID, &GuardSleep)> {
+        self.guards.iter().map(|(&guard, sleep)| (guard, sleep))
+    }
+
+    /// Strategy 1: the guard who slept the most minutes, and the minute they
+    /// were most often asleep.
+    pub fn strategy1(&self) -> Option<(GuardID, u32)> {
+        let (guard, sleep) = self
+            .guards()
+            .filter(|(_, sleep)| sleep.minutes_asleep > 0)
+            .max_by_key(|(_, sleep)| sleep.minutes_asleep)?;
+        let (minute, _) = sleep.sleepiest_minute()?;
+        Some((guard, minute))
+    }
+
+    /// Strategy 2: the guard who was most often asleep on the same minute,
+    /// and that minute.
+    pub fn strategy2(&self) -> Option<(GuardID, u32)> {
+        let (guard, (minute, _)) = self
+            .guards()
+            .filter_map(|(guard, sleep)| {
+                Some((guard, sleep.sleepiest_minute()?))
+            })
+            .max_by_key(|&(_, (_, times))| times)?;
+        Some((guard, minute))
+    }
+}
+
+impl fmt::Display for SleepReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Guard  Shifts  Asleep  Naps  Avg nap  Sleepiest minute")?;
+        for (guard, sleep) in self.guards() {
+            let average_nap = sleep
+                .average_nap()
+                .map_or(String::new(), |average| format!("{:.1}", average));
+            let sleepiest_minute = sleep
+                .sleepiest_minute()
+                .map_or(String::new(), |(minute, times)| {
+                    format!("00:{:02} ({} times)", minute, times)
+                });
+            let row = format!(
+                "{:>5}  {:>6}  {:>6}  {:>4}  {:>7}  {}",
+                guard.0,
+                sleep.shifts,
+                sleep.minutes_asleep,
+                sleep.naps,
+                average_nap,
+                sleepiest_minute
+            );
+            write!(f, "\n{}", row.trim_end())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use aoc_core::{Result, Solution};
+use std::collections::HashSet;
+
+fn reduced_len(polymer_string: &str) -> usize {
+    let mut polymer: Vec<char> = Vec::new();
+
+    for chr in polymer_string.chars() {
+        match polymer.last() {
+            Some(prev) if *prev != chr && prev.eq_ignore_ascii_case(&chr) => {
+                polymer.pop();
+            }
+            _ => polymer.push(chr),
+        }
+    }
+    polymer.len()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim_end().to_string())
+    }
+
+    fn part1(polymer_string: &Self::Input) -> Result<String> {
+        Ok(reduced_len(polymer_string).to_string())
+    }
+
+    fn part2(polymer_string: &Self::Input) -> Result<String> {
+        let mut distinct_units = HashSet::new();
+
+        for chr in polymer_string.chars() {
+            distinct_units.insert(chr.to_ascii_lowercase());
+        }
+
+        let best = distinct_units
+            .iter()
+            .map(|unit| {
+                reduced_len(
+                    &polymer_string
+                        .chars()
+                        .filter(|chr| chr.to_ascii_lowercase() != *unit)
+                        .collect::<String>(),
+                )
+            })
+            .min()
+            .ok_or("the polymer is empty")?;
+        Ok(best.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduced_len_examples() {
+        assert_eq!(reduced_len("aA"), 0);
+        assert_eq!(reduced_len("abBA"), 0);
+        assert_eq!(reduced_len("abAB"), 4);
+        assert_eq!(reduced_len("aabAAB"), 6);
+    }
+
+    #[test]
+    fn part1_example() {
+        let polymer = Day05::parse("dabAcCaCBAcCcaDA\n").unwrap();
+        assert_eq!(Day05::part1(&polymer).unwrap(), "10");
+    }
+
+    #[test]
+    fn part2_example() {
+        let polymer = Day05::parse("dabAcCaCBAcCcaDA\n").unwrap();
+        assert_eq!(Day05::part2(&polymer).unwrap(), "4");
+    }
+
+    #[test]
+    fn empty_polymer() {
+        let polymer = Day05::parse("\n").unwrap();
+        assert_eq!(Day05::part1(&polymer).unwrap(), "0");
+        assert!(Day05::part2(&polymer).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use aoc_core::{Result, Solution};
+use aoc_parse::{parse_lines, signed, Parse, Res};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag, combinator::map, error::context,
+    sequence::separated_pair,
+};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    fn distance(&self, other: &Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl Parse for Point {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "Point",
+            map(separated_pair(signed, tag(", "), signed), |(x, y)| Point {
+                x,
+                y,
+            }),
+        )(input)
+    }
+}
+
+/// The left, right, top and bottom edges of the smallest box around every
+/// point, or `None` if there are no points.
+fn bounds(points: &HashSet<Point>) -> Option<(i32, i32, i32, i32)> {
+    let left = points.iter().map(|point| point.x).min()?;
+    let right = points.iter().map(|point| point.x).max()?;
+    let top = points.iter().map(|point| point.y).min()?;
+    let bottom = points.iter().map(|point| point.y).max()?;
+    Some((left, right, top, bottom))
+}
+
+/// Part 2's limit on the total distance from a cell to every point.
+pub const MAX_TOTAL_DISTANCE: i32 = 10000;
+
+/// The number of cells whose total distance to every point is less than
+/// `max_total_distance`, or `None` if there are no points.
+pub fn safe_region_size(
+    points: &HashSet<Point>,
+    max_total_distance: i32,
+) -> Option<usize> {
+    let (left, right, top, bottom) = bounds(points)?;
+
+    let size = (left..(right + 1))
+        .cartesian_product(top..(bottom + 1))
+        .map(|grid_cell| Point {
+            x: grid_cell.0,
+            y: grid_cell.1,
+        })
+        .map(|grid_cell| {
+            points.iter().map(|point| grid_cell.distance(point)).sum()
+        })
+        .filter(|dist: &i32| *dist < max_total_distance)
+        .count();
+    Some(size)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = HashSet<Point>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let points: Vec<Point> = parse_lines(input)?;
+        Ok(points.into_iter().collect())
+    }
+
+    fn part1(points: &Self::Input) -> Result<String> {
+        let (left, right, top, bottom) =
+            bounds(points).ok_or("there are no points")?;
+        let mut nearest_point = HashMap::new();
+        for grid_cell in
+            (left..(right + 1)).cartesian_product(top..(bottom + 1))
+        {
+            let grid_cell = Point {
+                x: grid_cell.0,
+                y: grid_cell.1,
+            };
+            let mut min_dist = i32::MAX;
+            let mut min_point = None;
+            for point in points.iter() {
+                let dist = grid_cell.distance(point);
+                match dist.cmp(&min_dist) {
+                    Ordering::Less => {
+                        min_dist = dist;
+                        min_point = Some(point);
+                    }
+                    Ordering::Equal => {
+                        // If there's a tie, there's no "closest point"
+                        min_point = None;
+                    }
+                    Ordering::Greater => {}
+                }
+            }
+            nearest_point.insert(grid_cell, min_point);
+        }
+
+        let best = points
+            .iter()
+            .filter_map(|point| {
+                let nearest_to: Vec<_> = nearest_point
+                    .iter()
+                    .filter_map(|(key, val)| match val {
+                        Some(val) if *val == point => Some(key),
+                        _ => None,
+                    })
+                    .collect();
+                let nearest_on_border = nearest_to
+                    .iter()
+                    .filter(|near_point| {
+                        near_point.x == left
+                            || near_point.x == right
+                            || near_point.y == top
+                            || near_point.y == bottom
+                    })
+                    .count();
+                if nearest_on_border > 0 {
+                    None
+                } else {
+                    Some(nearest_to.len())
+                }
+            })
+            .max()
+            .ok_or("every point's area is infinite")?;
+        Ok(best.to_string())
+    }
+
+    fn part2(points: &Self::Input) -> Result<String> {
+        let size = safe_region_size(points, MAX_TOTAL_DISTANCE)
+            .ok_or("there are no points")?;
+        Ok(size.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9\n";
+
+    #[test]
+    fn part1_example() {
+        let points = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(Day06::part1(&points).unwrap(), "17");
+    }
+
+    #[test]
+    fn safe_region_example() {
+        let points = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(safe_region_size(&points, 32), Some(16));
+    }
+
+    #[test]
+    fn degenerate_inputs() {
+        let points = Day06::parse("3, 3\n").unwrap();
+        let err = Day06::part1(&points).unwrap_err();
+        assert_eq!(err.to_string(), "every point's area is infinite");
+
+        let points = Day06::parse("").unwrap();
+        assert!(Day06::part1(&points).is_err());
+        assert!(Day06::part2(&points).is_err());
+        assert_eq!(safe_region_size(&points, 32), None);
+    }
+}
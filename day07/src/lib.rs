@@ -0,0 +1,387 @@
+use aoc_core::Solution;
+use aoc_graph::TaskGraph;
+use aoc_parse::{parse_lines, Parse, Res};
+use nom::{
+    bytes::complete::tag, character::complete::alphanumeric1, combinator::map,
+    error::context, sequence::tuple,
+};
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Default)]
+pub struct Step(String);
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub struct Dependency {
+    depender: Step,
+    prereq: Step,
+}
+
+impl Parse for Step {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "Step",
+            map(alphanumeric1, |name: &str| Step(name.to_string())),
+        )(input)
+    }
+}
+
+impl Parse for Dependency {
+    fn nom_parse(input: &str) -> Res<&str, Self> {
+        context(
+            "Dependency",
+            map(
+                tuple((
+                    tag("Step "),
+                    Step::nom_parse,
+                    tag(" must be finished before step "),
+                    Step::nom_parse,
+                    tag(" can begin."),
+                )),
+                |(_, prereq, _, depender, _)| Dependency { prereq, depender },
+            ),
+        )(input)
+    }
+}
+
+/// Number of workers available in part 2.
+pub const WORKERS: usize = 5;
+/// Time every step takes in part 2 on top of its position in the alphabet.
+pub const BASE_DURATION: u32 = 60;
+
+/// Why the steps can't all be completed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StepError {
+    /// Each step in `cycle` must be finished before the next, and the last
+    /// before the first. Those steps, and the `unreachable` ones that depend
+    /// on them, can never begin.
+    Cycle {
+        cycle: Vec<Step>,
+        unreachable: Vec<Step>,
+    },
+    /// Only steps named by a single letter from A to Z have a duration.
+    UnknownDuration(Step),
+    /// There are steps to do but no workers to do them.
+    NoWorkers,
+    /// Finishing every step would take more than `u32::MAX` seconds.
+    TooLong,
+}
+
+impl fmt::Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StepError::Cycle { cycle, unreachable } => {
+                write!(f, "steps form a cycle: ")?;
+                for step in cycle.iter() {
+                    write!(f, "{} -> ", step)?;
+                }
+                write!(f, "{}", cycle[0])?;
+                if !unreachable.is_empty() {
+                    let names: Vec<_> = unreachable
+                        .iter()
+                        .map(|step| step.0.as_str())
+                        .collect();
+                    write!(f, "; unreachable steps: {}", names.join(", "))?;
+                }
+                Ok(())
+            }
+            StepError::UnknownDuration(step) => {
+                write!(f, "no duration for step {}", step)
+            }
+            StepError::NoWorkers => write!(f, "there are no workers"),
+            StepError::TooLong => {
+                write!(f, "the steps would take too long to finish")
+            }
+        }
+    }
+}
+
+impl Error for StepError {}
+
+fn task_time(step: &Step, base_duration: u32) -> Result<u32, StepError> {
+    match step.0.as_bytes() {
+        [letter @ b'A'..=b'Z'] => base_duration
+            .checked_add((letter - b'A' + 1) as u32)
+            .ok_or(StepError::TooLong),
+        _ => Err(StepError::UnknownDuration(step.clone())),
+    }
+}
+
+fn graph(dependencies: &[Dependency]) -> TaskGraph<Step> {
+    let mut graph = TaskGraph::new();
+    for dependency in dependencies.iter() {
+        graph.add_dependency(
+            dependency.prereq.clone(),
+            dependency.depender.clone(),
+        );
+    }
+    graph
+}
+
+/// The order the steps should be completed in, alphabetically first when
+/// there's a choice.
+pub fn step_order(dependencies: &[Dependency]) -> Result<Vec<Step>, StepError> {
+    let graph = graph(dependencies);
+    graph.topological_order().map_err(|cycle| {
+        let unreachable = graph
+            .unreachable()
+            .into_iter()
+            .filter(|step| !cycle.0.contains(step))
+            .collect();
+        StepError::Cycle {
+            cycle: cycle.0,
+            unreachable,
+        }
+    })
+}
+
+/// The result of working through every step with a team of workers.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Schedule {
+    /// Seconds until the last step is finished.
+    pub total_time: u32,
+    /// For each worker, every step they were given, with the seconds they
+    /// started and finished it, in order.
+    pub assignments: Vec<Vec<(Step, u32, u32)>>,
+}
+
+impl Schedule {
+    /// The step each worker is busy with during `second`, if any.
+    pub fn at(&self, second: u32) -> Vec<Option<Step>> {
+        self.assignments
+            .iter()
+            .map(|tasks| {
+                let index =
+                    tasks.partition_point(|(_, _, finish)| *finish <= second);
+                tasks
+                    .get(index)
+                    .filter(|(_, start, _)| *start <= second)
+                    .map(|(step, _, _)| step.clone())
+            })
+            .collect()
+    }
+
+    /// For each second, the step each worker is busy with, if any. Worked
+    /// out a second at a time, since long schedules are too big to hold all
+    /// at once.
+    pub fn trace(&self) -> impl Iterator<Item = Vec<Option<Step>>> + '_ {
+        (0..self.total_time).map(move |second| self.at(second))
+    }
+}
+
+/// Simulates `workers` workers completing every step, where a step takes
+/// `base_duration` seconds plus its position in the alphabet. Idle workers
+/// always pick up the alphabetically first available step. Fails if there
+/// are steps but no workers, or if they'd take more than `u32::MAX` seconds.
+pub fn schedule(
+    dependencies: &[Dependency],
+    workers: usize,
+    base_duration: u32,
+) -> Result<Schedule, StepError> {
+    let mut steps = step_order(dependencies)?;
+    steps.sort();
+    for step in steps.iter() {
+        task_time(step, base_duration)?;
+    }
+    if workers == 0 && !steps.is_empty() {
+        return Err(StepError::NoWorkers);
+    }
+
+    let graph = graph(dependencies);
+    let mut number_of_prereqs_left = graph.number_of_prereqs();
+
+    let mut available = BinaryHeap::new();
+    for (step, num_prereqs) in number_of_prereqs_left.iter() {
+        if *num_prereqs == 0 {
+            available.push(Reverse(step.clone()));
+        }
+    }
+
+    // (step, start, finish) for everything handed out to each worker
+    let mut assignments: Vec<Vec<(Step, u32, u32)>> = vec![Vec::new(); workers];
+    let mut active_tasks: Vec<Option<(Step, u32)>> = vec![None; workers];
+    let mut now: u32 = 0;
+    loop {
+        for (worker, task) in active_tasks.iter_mut().enumerate() {
+            if task.is_none() {
+                if let Some(Reverse(step)) = available.pop() {
+                    let finish = now
+                        .checked_add(task_time(&step, base_duration)?)
+                        .ok_or(StepError::TooLong)?;
+                    assignments[worker].push((step.clone(), now, finish));
+                    *task = Some((step, finish));
+                }
+            }
+        }
+
+        // Jump straight to the next time a worker finishes something
+        now = match active_tasks.iter().flatten().map(|(_, t)| *t).min() {
+            Some(next_finish) => next_finish,
+            None => break,
+        };
+        for task in active_tasks.iter_mut() {
+            match task {
+                Some((step, finish)) if *finish == now => {
+                    for depender in graph.dependers(step) {
+                        let num_prereqs =
+                            number_of_prereqs_left.get_mut(depender).unwrap();
+                        *num_prereqs -= 1;
+                        if *num_prereqs == 0 {
+                            available.push(Reverse(depender.clone()));
+                        }
+                    }
+                    *task = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(Schedule {
+        total_time: now,
+        assignments,
+    })
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Dependency>;
+
+    fn parse(input: &str) -> aoc_core::Result<Self::Input> {
+        Ok(parse_lines(input)?)
+    }
+
+    fn part1(dependencies: &Self::Input) -> aoc_core::Result<String> {
+        let names: Vec<_> = step_order(dependencies)?
+            .into_iter()
+            .map(|step| step.0)
+            .collect();
+        // One-letter names run together as the puzzle expects, but longer
+        // ones need separating to be told apart
+        if names.iter().all(|name| name.len() == 1) {
+            Ok(names.concat())
+        } else {
+            Ok(names.join(","))
+        }
+    }
+
+    fn part2(dependencies: &Self::Input) -> aoc_core::Result<String> {
+        let schedule = schedule(dependencies, WORKERS, BASE_DURATION)?;
+        Ok(schedule.total_time.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.
+";
+
+    fn steps(names: &[&str]) -> Vec<Step> {
+        names.iter().map(|name| Step(name.to_string())).collect()
+    }
+
+    #[test]
+    fn part1_example() {
+        let dependencies = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(Day07::part1(&dependencies).unwrap(), "CABDFE");
+    }
+
+    #[test]
+    fn schedule_example() {
+        let dependencies = Day07::parse(EXAMPLE).unwrap();
+        let schedule = schedule(&dependencies, 2, 0).unwrap();
+        assert_eq!(schedule.total_time, 15);
+        assert_eq!(schedule.trace().count(), 15);
+        assert_eq!(
+            schedule.at(3),
+            vec![Some(Step("A".into())), Some(Step("F".into()))]
+        );
+        assert_eq!(schedule.at(14), vec![Some(Step("E".into())), None]);
+        assert_eq!(schedule.at(15), vec![None, None]);
+        assert_eq!(
+            schedule.trace().next(),
+            Some(vec![Some(Step("C".into())), None])
+        );
+    }
+
+    #[test]
+    fn multi_character_names() {
+        let dependencies = Day07::parse(
+            "Step build must be finished before step test can begin.\n\
+             Step fetch must be finished before step build can begin.\n",
+        )
+        .unwrap();
+        assert_eq!(
+            step_order(&dependencies).unwrap(),
+            steps(&["fetch", "build", "test"])
+        );
+        assert_eq!(Day07::part1(&dependencies).unwrap(), "fetch,build,test");
+        assert_eq!(
+            schedule(&dependencies, 2, 0),
+            Err(StepError::UnknownDuration(Step("build".into())))
+        );
+    }
+
+    #[test]
+    fn no_workers() {
+        let dependencies = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(schedule(&dependencies, 0, 0), Err(StepError::NoWorkers));
+        assert_eq!(schedule(&[], 0, 0).unwrap().total_time, 0);
+    }
+
+    #[test]
+    fn long_schedules() {
+        let dependencies = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            schedule(&dependencies, 1, u32::MAX - 5),
+            Err(StepError::TooLong)
+        );
+        assert_eq!(
+            schedule(&dependencies, 1, u32::MAX / 5),
+            Err(StepError::TooLong)
+        );
+
+        // Long enough that holding every second at once wouldn't fit
+        let schedule = schedule(&dependencies, 2, 1_000_000_000).unwrap();
+        assert_eq!(schedule.total_time, 4_000_000_018);
+        assert_eq!(
+            schedule.at(4_000_000_017),
+            vec![Some(Step("E".into())), None]
+        );
+    }
+
+    #[test]
+    fn cycle_is_reported() {
+        let input = format!(
+            "{}Step E must be finished before step C can begin.\n\
+             Step G must be finished before step H can begin.\n",
+            EXAMPLE
+        );
+        let dependencies = Day07::parse(&input).unwrap();
+        assert_eq!(
+            step_order(&dependencies),
+            Err(StepError::Cycle {
+                cycle: steps(&["A", "B", "E", "C"]),
+                unreachable: steps(&["D", "F"]),
+            })
+        );
+    }
+}
@@ -1,30 +1,21 @@
+use aoc_core::{Result, Solution};
+use aoc_parse::{number, parse_all, Parse, Res};
 use nom::{
-    character::complete::{char, digit1},
-    combinator::{flat_map, map, map_res},
+    character::complete::char,
+    combinator::{flat_map, map},
     error::context,
     multi::count,
     sequence::{pair, preceded, separated_pair},
 };
 
 use std::collections::HashMap;
-use std::error::Error;
-use std::fs;
 
 #[derive(Debug)]
-struct TreeNode {
+pub struct TreeNode {
     children: Vec<TreeNode>,
     metadata: Vec<u32>,
 }
 
-type Res<T, U> = nom::IResult<T, U, nom::error::VerboseError<T>>;
-trait Parse: Sized {
-    fn nom_parse(input: &str) -> Res<&str, Self>;
-}
-
-fn number(input: &str) -> Res<&str, u32> {
-    context("number", map_res(digit1, str::parse::<u32>))(input)
-}
-
 #[derive(Debug, Clone)]
 struct Header {
     num_children: usize,
@@ -79,7 +70,8 @@ fn tree_value(tree: &TreeNode) -> u32 {
     let child_indices = tree
         .metadata
         .iter()
-        .map(|data| (*data as usize) - 1) // switch to 0-indexing
+        // Switch to 0-indexing. A 0 entry refers to no child, so is skipped
+        .filter_map(|data| (*data as usize).checked_sub(1))
         .filter(|index| *index < tree.children.len());
     let mut multiplicity: HashMap<usize, u32> = HashMap::new();
 
@@ -96,9 +88,47 @@ fn tree_value(tree: &TreeNode) -> u32 {
         .sum()
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string("input")?;
-    let tree = TreeNode::nom_parse(&contents).unwrap().1;
-    println!("{}", tree_value(&tree));
-    Ok(())
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = TreeNode;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_all(input)?)
+    }
+
+    fn part1(tree: &Self::Input) -> Result<String> {
+        Ok(sum_metadata(tree).to_string())
+    }
+
+    fn part2(tree: &Self::Input) -> Result<String> {
+        Ok(tree_value(tree).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2\n";
+
+    #[test]
+    fn part1_example() {
+        let tree = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(Day08::part1(&tree).unwrap(), "138");
+    }
+
+    #[test]
+    fn part2_example() {
+        let tree = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(Day08::part2(&tree).unwrap(), "66");
+    }
+
+    #[test]
+    fn zero_metadata_refers_to_no_child() {
+        let tree = Day08::parse("1 1 0 1 99 0\n").unwrap();
+        assert_eq!(Day08::part2(&tree).unwrap(), "0");
+        let tree = Day08::parse("1 2 0 1 99 0 1\n").unwrap();
+        assert_eq!(Day08::part2(&tree).unwrap(), "99");
+    }
 }